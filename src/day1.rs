@@ -1,68 +1,273 @@
-use anyhow::{anyhow, Result};
+use crate::mem;
+use crate::num::{self, Int};
+use crate::runner;
+use crate::scanner::{Match, Scanner};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use std::fmt::Display;
+use std::path::Path;
 
-fn get_digit(input: &str) -> Option<u32> {
-    if let Some(first) = input.chars().next() {
-        if let Some(digit) = first.to_digit(10) {
-            return Some(digit);
+const ENGLISH: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+const ROMAN: [&str; 9] = ["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX"];
+
+/// One source of digit tokens. A vocabulary is a comma separated list of these, such as
+/// `digits,english` or `digits,numbers/german.txt`.
+#[derive(Debug, PartialEq, Eq)]
+enum Tokenizer {
+    Digits,
+    English,
+    Roman,
+    /// Words loaded from a file, one `word value` pair per line.
+    Table(Vec<(String, u32)>),
+}
+
+impl Tokenizer {
+    fn parse(spec: &str) -> Result<Tokenizer> {
+        match spec {
+            "digits" => Ok(Tokenizer::Digits),
+            "english" => Ok(Tokenizer::English),
+            "roman" => Ok(Tokenizer::Roman),
+            path if path.contains(std::path::is_separator) || Path::new(path).exists() => {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("Could not read the table {:?}", path))?;
+
+                Ok(Tokenizer::Table(
+                    parse_table(&content).with_context(|| format!("In table {:?}", path))?,
+                ))
+            }
+            name => bail!(
+                "Unknown tokenizer {:?}, expected digits, english, roman or a table file",
+                name
+            ),
         }
     }
 
-    for (index, text) in [
-        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-    ]
-    .iter()
-    .enumerate()
-    {
-        if input.starts_with(text) {
-            return Some(index as u32);
+    fn tokens(&self) -> Vec<(String, u32)> {
+        match self {
+            Tokenizer::Digits => (0..10).map(|digit| (digit.to_string(), digit)).collect(),
+            Tokenizer::English => ENGLISH.iter().map(|w| w.to_string()).zip(0..).collect(),
+            Tokenizer::Roman => ROMAN.iter().map(|r| r.to_string()).zip(1..).collect(),
+            Tokenizer::Table(words) => words.clone(),
         }
     }
+}
+
+/// Reads `word value` lines, skipping blank lines and `#` comments.
+fn parse_table(content: &str) -> Result<Vec<(String, u32)>> {
+    let mut words = vec![];
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (word, value) = line
+            .split_once(char::is_whitespace)
+            .ok_or(anyhow!("line {}: expected \"word value\"", index + 1))?;
+        let value: u32 = value
+            .trim()
+            .parse()
+            .with_context(|| format!("line {}: bad value for {:?}", index + 1, word))?;
+
+        if value > 9 {
+            bail!(
+                "line {}: {:?} stands for {}, not a digit",
+                index + 1,
+                word,
+                value
+            );
+        }
 
-    None
+        words.push((word.to_string(), value));
+    }
+
+    Ok(words)
+}
+
+/// Finds the first and last digit of each line with whatever tokens the vocabulary has.
+struct Calibration {
+    scanner: Scanner,
 }
 
-fn get_digits(line: &str) -> Result<u32> {
-    let len = line.len();
+impl Calibration {
+    fn new(vocabulary: &str) -> Result<Calibration> {
+        let tokenizers = vocabulary
+            .split(',')
+            .map(|spec| Tokenizer::parse(spec.trim()))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Calibration {
+            scanner: Scanner::new(tokenizers.iter().flat_map(Tokenizer::tokens)),
+        })
+    }
+
+    /// The calibration value of a line, or `None` if it has no digit at all.
+    fn value<T: Int>(&self, line: &str) -> Result<Option<T>> {
+        let (Some(first), Some(last)) = (self.scanner.first(line), self.scanner.last(line)) else {
+            return Ok(None);
+        };
 
-    let mut first: Option<u32> = None;
-    let mut last: Option<u32> = None;
+        let tens = num::mul(
+            num::cast(first.value, "first digit")?,
+            num::cast(10u32, "base")?,
+            "tens",
+        )?;
 
-    for i in 0..len {
-        if let Some(digit) = get_digit(&line[i..len]) {
-            if first.is_none() {
-                first = Some(digit);
+        num::add(
+            tens,
+            num::cast(last.value, "last digit")?,
+            "calibration value",
+        )
+        .map(Some)
+    }
+
+    /// Adds up every line's value. Skipped lines are left to [`Calibration::explain`] to list, so a
+    /// rerun at a wider type doesn't report them again.
+    fn sum<T: Int>(&self, lines: &[String], policy: Policy) -> Result<T> {
+        let mut sum = T::ZERO;
+
+        for (index, line) in lines.iter().enumerate() {
+            match (self.value(line)?, policy) {
+                (Some(value), _) => sum = num::add(sum, value, "calibration sum")?,
+                (None, Policy::Skip) => {}
+                (None, Policy::Fail) => bail!("line {}: expected a digit in {:?}", index + 1, line),
             }
+        }
+
+        Ok(sum)
+    }
 
-            last = Some(digit);
+    fn explain(&self, lines: &[String]) -> Report {
+        let token = |line: &str, found: Match| Token {
+            text: line[found.start..found.end].to_string(),
+            column: line[..found.start].chars().count() + 1,
+            value: found.value,
+        };
+        let mut report = Report {
+            lines: vec![],
+            missing: vec![],
+        };
+
+        for (index, line) in lines.iter().enumerate() {
+            match (self.scanner.first(line), self.scanner.last(line)) {
+                (Some(first), Some(last)) => report.lines.push(LineReport {
+                    line: index + 1,
+                    value: first.value * 10 + last.value,
+                    first: token(line, first),
+                    last: token(line, last),
+                }),
+                _ => report.missing.push(index + 1),
+            }
         }
+
+        report
     }
+}
 
-    let first = first.ok_or(anyhow!("expected first digit"))?;
-    let last = last.ok_or(anyhow!("expected last digit"))?;
+/// What to do with a line that has no digit, set with `--param missing=skip|fail`. `--explain`
+/// lists the lines that were skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Policy {
+    Skip,
+    Fail,
+}
+
+impl Policy {
+    fn from_param() -> Result<Policy> {
+        match runner::param("missing").as_deref() {
+            None | Some("fail") => Ok(Policy::Fail),
+            Some("skip") => Ok(Policy::Skip),
+            Some(x) => bail!("Expected missing=skip or missing=fail, got {:?}", x),
+        }
+    }
+}
+
+/// A matched token, with its 1-based column counted in characters.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct Token {
+    text: String,
+    column: usize,
+    value: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct LineReport {
+    line: usize,
+    first: Token,
+    last: Token,
+    value: u32,
+}
 
-    Ok(first * 10 + last)
+/// What `--explain` shows: how every line was read, and which lines had no digit.
+#[derive(Debug, Serialize)]
+struct Report {
+    lines: Vec<LineReport>,
+    missing: Vec<usize>,
 }
 
-fn sum_lines(lines: &Vec<String>) -> Result<u32> {
-    let mut sum = 0;
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in self.lines.iter() {
+            writeln!(
+                f,
+                "line {:>4}: {:?} at {} .. {:?} at {} => {}",
+                line.line,
+                line.first.text,
+                line.first.column,
+                line.last.text,
+                line.last.column,
+                line.value
+            )?;
+        }
+
+        if !self.missing.is_empty() {
+            writeln!(f, "no digit on lines {}", join(&self.missing))?;
+        }
 
-    for line in lines.iter() {
-        sum += get_digits(line)?;
+        Ok(())
     }
+}
 
-    Ok(sum)
+fn join(numbers: &[usize]) -> String {
+    numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
-fn process(input: &str) -> Result<u32> {
-    sum_lines(&input.lines().map(|l| l.to_string()).collect())
+fn parse(input: &str) -> Vec<String> {
+    input.lines().map(|l| l.to_string()).collect()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::testing::Xorshift;
+
+    fn process<T: Int>(vocabulary: &str, input: &str) -> Result<T> {
+        Calibration::new(vocabulary)?.sum(&parse(input), Policy::Fail)
+    }
 
     #[test]
-    fn test() {
+    fn digits() -> Result<()> {
+        let input = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
+        assert_eq!(process::<u32>("digits", input)?, 142);
+
+        Ok(())
+    }
+
+    #[test]
+    fn english() -> Result<()> {
         let input = "two1nine
 eightwothree
 abcone2threexyz
@@ -71,15 +276,210 @@ xtwone3four
 zoneight234
 7pqrstsixteen";
 
-        assert_eq!(process(input).unwrap(), 281);
+        assert_eq!(process::<u32>("digits,english", input)?, 281);
+
+        Ok(())
     }
+
+    #[test]
+    fn non_ascii_noise() -> Result<()> {
+        let input = "é1ü二two€
+ñineight
+🦀seven👀six🦀";
+
+        assert_eq!(process::<u32>("digits,english", input)?, 12 + 88 + 76);
+        assert_eq!(process::<u32>("digits", "é1ü二€")?, 11);
+
+        Ok(())
+    }
+
+    #[test]
+    fn missing_digits() -> Result<()> {
+        let calibration = Calibration::new("digits,english")?;
+        let lines = parse("a1b\nnothing\nétwo3\n\n");
+
+        let error = calibration.sum::<u32>(&lines, Policy::Fail).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected a digit in \"nothing\"");
+        assert_eq!(calibration.sum::<u32>(&lines, Policy::Skip)?, 11 + 23);
+
+        let report = calibration.explain(&lines);
+        assert_eq!(report.missing, vec![2, 4]);
+        assert_eq!(
+            report.lines[1],
+            LineReport {
+                line: 3,
+                first: Token {
+                    text: "two".to_string(),
+                    column: 2,
+                    value: 2
+                },
+                last: Token {
+                    text: "3".to_string(),
+                    column: 5,
+                    value: 3
+                },
+                value: 23,
+            }
+        );
+        assert_eq!(
+            report.to_string(),
+            "line    1: \"1\" at 2 .. \"1\" at 2 => 11\n\
+             line    3: \"two\" at 2 .. \"3\" at 5 => 23\n\
+             no digit on lines 2, 4\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn roman() -> Result<()> {
+        assert_eq!(process::<u32>("roman", "xVIIIyIIz\nIXaIV")?, 82 + 94);
+        assert!(process::<u32>("roman", "abc").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn word_table() -> Result<()> {
+        let table = parse_table("# German\neins 1\n\nzwei 2\n  drei\t3\n")?;
+
+        assert_eq!(
+            table,
+            vec![
+                ("eins".to_string(), 1),
+                ("zwei".to_string(), 2),
+                ("drei".to_string(), 3)
+            ]
+        );
+        assert!(parse_table("elf 11").is_err());
+        assert!(parse_table("eins").is_err());
+        assert_eq!(
+            format!("{:#}", Tokenizer::parse("no/such/table.txt").unwrap_err()),
+            "Could not read the table \"no/such/table.txt\": No such file or directory (os error 2)"
+        );
+        assert_eq!(
+            Tokenizer::parse("englsh").unwrap_err().to_string(),
+            "Unknown tokenizer \"englsh\", expected digits, english, roman or a table file"
+        );
+
+        let calibration = Calibration {
+            scanner: Scanner::new(Tokenizer::Table(table).tokens()),
+        };
+        assert_eq!(calibration.value::<u32>("xdreieinszwei")?, Some(32));
+
+        Ok(())
+    }
+
+    /// The scan this day used before the automaton: every word compared at every byte offset.
+    fn naive_digits(line: &str) -> Option<(u32, u32)> {
+        let words = [
+            "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        let digits: Vec<u32> = line
+            .char_indices()
+            .filter_map(|(i, _)| {
+                let rest = &line[i..];
+
+                rest.chars()
+                    .next()
+                    .and_then(|c| c.to_digit(10))
+                    .or_else(|| {
+                        words
+                            .iter()
+                            .position(|word| rest.starts_with(word))
+                            .map(|digit| digit as u32)
+                    })
+            })
+            .collect();
+
+        Some((*digits.first()?, *digits.last()?))
+    }
+
+    fn generated_lines(count: usize, len: usize) -> Vec<String> {
+        let pieces = [
+            "eightwo", "x", "nin", "one", "q", "7", "thre", "sevenine", "z", "é", "二", "🦀",
+        ];
+        let mut random = Xorshift::default();
+
+        (0..count)
+            .map(|_| {
+                let mut line = String::new();
+
+                while line.len() < len {
+                    line.push_str(pieces[random.below(pieces.len())]);
+                }
+
+                line
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_naive_scan() {
+        let scanner = Calibration::new("digits,english").unwrap().scanner;
+
+        for line in generated_lines(2000, 40) {
+            let scanned = scanner
+                .first(&line)
+                .zip(scanner.last(&line))
+                .map(|(first, last)| (first.value, last.value));
+
+            assert_eq!(scanned, naive_digits(&line), "{}", line);
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_against_naive_scan() {
+        let scanner = Calibration::new("digits,english").unwrap().scanner;
+
+        for len in [40, 1000, 100_000] {
+            let lines = generated_lines(4_000_000 / len, len);
+
+            let started = std::time::Instant::now();
+            let naive: u64 = lines
+                .iter()
+                .filter_map(|line| naive_digits(line))
+                .map(|(first, last)| (first * 10 + last) as u64)
+                .sum();
+            let naive_time = started.elapsed();
+
+            let started = std::time::Instant::now();
+            let scanned: u64 = lines
+                .iter()
+                .filter_map(|line| scanner.first(line).zip(scanner.last(line)))
+                .map(|(first, last)| (first.value * 10 + last.value) as u64)
+                .sum();
+            let scanned_time = started.elapsed();
+
+            assert_eq!(naive, scanned);
+            println!(
+                "{} lines of {} bytes: naive {:?}, automaton {:?}",
+                lines.len(),
+                len,
+                naive_time,
+                scanned_time
+            );
+        }
+    }
+}
+
+pub fn part_1() -> Result<String> {
+    run(1, "digits")
 }
 
-pub fn main() -> Result<()> {
-    println!(
-        "1.2: {}",
-        process(
-            "ckmb52fldxkseven3fkjgcbzmnr7
+pub fn part_2() -> Result<String> {
+    run(2, "digits,english")
+}
+
+/// Solves with `preset` unless the runner was given `--param vocabulary.1=...` or
+/// `--param vocabulary.2=...` for this part.
+fn run(part: u32, preset: &str) -> Result<String> {
+    let vocabulary = runner::param(&format!("vocabulary.{}", part)).unwrap_or(preset.to_string());
+    let calibration = Calibration::new(&vocabulary)?;
+    let policy = Policy::from_param()?;
+
+    let input = "ckmb52fldxkseven3fkjgcbzmnr7
 gckhqpb6twoqnjxqplthree2fourkspnsnzxlz1
 2onetwocrgbqm7
 frkh2nineqmqxrvdsevenfive
@@ -1078,9 +1478,19 @@ pseven3threeeightseven
 five2two7hstbbqzrninegbtwo2
 eightfblzpmhs4
 fbbdeightzzsdffh8jbjzxkclj
-3nine6five1"
-        )?
-    );
+3nine6five1";
+    let lines = mem::phase("parse", || parse(input));
+
+    if runner::explaining() {
+        runner::explain(&calibration.explain(&lines));
+    }
+
+    let result = mem::phase("solve", || {
+        num::widen(
+            || calibration.sum::<u32>(&lines, policy),
+            || calibration.sum::<u128>(&lines, policy),
+        )
+    })?;
 
-    Ok(())
+    Ok(result)
 }
@@ -1,5 +1,6 @@
-use anyhow::Result;
-use std::collections::HashSet;
+use crate::graph::{cycle_through, Grid};
+use crate::mem;
+use anyhow::{anyhow, Result};
 
 use nom::{
     branch::alt,
@@ -24,85 +25,28 @@ enum Cell {
 
 impl Cell {
     fn open_north(&self) -> bool {
-        match self {
-            Cell::Starting | Cell::YPipe | Cell::NETurn | Cell::NWTurn => true,
-            _ => false,
-        }
+        matches!(
+            self,
+            Cell::Starting | Cell::YPipe | Cell::NETurn | Cell::NWTurn
+        )
     }
     fn open_south(&self) -> bool {
-        match self {
-            Cell::Starting | Cell::YPipe | Cell::SETurn | Cell::SWTurn => true,
-            _ => false,
-        }
+        matches!(
+            self,
+            Cell::Starting | Cell::YPipe | Cell::SETurn | Cell::SWTurn
+        )
     }
     fn open_west(&self) -> bool {
-        match self {
-            Cell::Starting | Cell::XPipe | Cell::NWTurn | Cell::SWTurn => true,
-            _ => false,
-        }
+        matches!(
+            self,
+            Cell::Starting | Cell::XPipe | Cell::NWTurn | Cell::SWTurn
+        )
     }
     fn open_east(&self) -> bool {
-        match self {
-            Cell::Starting | Cell::XPipe | Cell::NETurn | Cell::SETurn => true,
-            _ => false,
-        }
-    }
-}
-
-#[derive(Clone, Debug, Hash, PartialEq, PartialOrd, Ord, Eq)]
-struct Point {
-    x: usize,
-    y: usize,
-}
-
-impl Point {
-    fn next_to(&self, other: &Self) -> bool {
-        (self.x == other.x && (self.y + 1 == other.y || self.y == other.y + 1))
-            || (self.y == other.y && (self.x + 1 == other.x || self.x == other.x + 1))
-    }
-
-    fn north(&self) -> Option<Point> {
-        if self.y > 0 {
-            Some(Point {
-                x: self.x,
-                y: self.y - 1,
-            })
-        } else {
-            None
-        }
-    }
-
-    fn south(&self, max: usize) -> Option<Point> {
-        if self.y < max {
-            Some(Point {
-                x: self.x,
-                y: self.y + 1,
-            })
-        } else {
-            None
-        }
-    }
-
-    fn west(&self) -> Option<Point> {
-        if self.x > 0 {
-            Some(Point {
-                x: self.x - 1,
-                y: self.y,
-            })
-        } else {
-            None
-        }
-    }
-
-    fn east(&self, max: usize) -> Option<Point> {
-        if self.x < max {
-            Some(Point {
-                x: self.x + 1,
-                y: self.y,
-            })
-        } else {
-            None
-        }
+        matches!(
+            self,
+            Cell::Starting | Cell::XPipe | Cell::NETurn | Cell::SETurn
+        )
     }
 }
 
@@ -124,83 +68,51 @@ fn parse(input: &str) -> IResult<&str, Map> {
     )(input)
 }
 
-fn get_loop_length(starting_point: Point, map: &Map) -> usize {
-    let mut path = HashSet::new();
-    let mut point = starting_point.clone();
-
-    loop {
-        if let Some(next) = point.north() {
-            if !path.contains(&next)
-                && map[point.y][point.x].open_north()
-                && map[next.y][next.x].open_south()
-            {
-                path.insert(point);
-                point = next;
-                continue;
-            }
-        }
+fn connected(map: &Map, (x, y): (usize, usize), (nx, ny): (usize, usize)) -> bool {
+    let (from, to) = (map[y][x], map[ny][nx]);
 
-        if let Some(next) = point.south(map.len() - 1) {
-            if !path.contains(&next)
-                && map[point.y][point.x].open_south()
-                && map[next.y][next.x].open_north()
-            {
-                path.insert(point);
-                point = next;
-                continue;
-            }
-        }
-
-        if let Some(next) = point.west() {
-            if !path.contains(&next)
-                && map[point.y][point.x].open_west()
-                && map[next.y][next.x].open_east()
-            {
-                path.insert(point);
-                point = next;
-                continue;
-            }
-        }
+    if ny < y {
+        from.open_north() && to.open_south()
+    } else if ny > y {
+        from.open_south() && to.open_north()
+    } else if nx < x {
+        from.open_west() && to.open_east()
+    } else {
+        from.open_east() && to.open_west()
+    }
+}
 
-        if let Some(next) = point.east(map[point.y].len() - 1) {
-            if !path.contains(&next)
-                && map[point.y][point.x].open_east()
-                && map[next.y][next.x].open_west()
-            {
-                path.insert(point);
-                point = next;
-                continue;
-            }
-        }
+fn get_loop_length(starting_point: (usize, usize), map: &Map) -> Result<usize> {
+    let width = map.iter().map(|line| line.len()).min().unwrap_or_default();
+    let grid = Grid::new(width, map.len(), |from, to| {
+        connected(map, from, to).then_some(1)
+    });
 
-        if point.next_to(&starting_point) {
-            break;
-        }
-    }
+    let path = cycle_through(&grid, starting_point)
+        .ok_or(anyhow!("Expected a loop through the starting point"))?;
 
-    (path.len() + 1) / 2
+    Ok(path.len() / 2)
 }
 
-fn process(input: &str) -> Result<usize> {
-    let (_, map) = parse(input).map_err(|e| e.to_owned())?;
-
+fn solve(map: &Map) -> Result<usize> {
     let starting_point = map
         .iter()
         .enumerate()
         .find_map(|(y, line)| {
-            if let Some(x) =
-                line.iter()
-                    .enumerate()
-                    .find_map(|(x, c)| if c == &Cell::Starting { Some(x) } else { None })
-            {
-                Some(Point { x, y })
-            } else {
-                None
-            }
+            line.iter()
+                .position(|c| c == &Cell::Starting)
+                .map(|x| (x, y))
         })
-        .expect("a starting point");
+        .ok_or(anyhow!("Expected a starting point"))?;
 
-    Ok(get_loop_length(starting_point, &map))
+    get_loop_length(starting_point, map)
+}
+
+#[cfg(test)]
+fn process(input: &str) -> Result<usize> {
+    let (_, map) = parse(input).map_err(|e| e.to_owned())?;
+
+    solve(&map)
 }
 
 #[cfg(test)]
@@ -222,7 +134,7 @@ mod tests {
     }
 }
 
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "F---7-FJ.7--FJ7.-.7-|7F.|7FJ77F7J-|--.FLF|.F-7-F7.F.J77|--|7--|7|-J-F--7-F7.F7-F7F7F-|-|-F777L7|-.FLF|-77F7-FJF|JF--F.77J-L7-FF--7.FFF7FF7.7
                  |J.F|7F|F7J.J-L-|-|.L7F7JF7-7-LJ|LJ.F7.L|.F|F7FJ7.F7.|F|L7.L7L|-F-|7|L|JFF.F||F777L|-77L-|JF7FJJJLF7LL7|.L77|L7LLLJ.|..LLL7|L7-.-JFFLL-7L-FF
                  F7L7JLLJL-7FL7|F7-J7.-J.7L7J|.|-JF|FLFF7L-JLL|-F7-F|77|.|L7FJ7JJFJL7JJJFF.FJ||||JFF|LL7F-JF|.LJ7F.|F7F|J7LLL-7||||LFFFF77|-L7LL7F7-JL7|FF7L|
@@ -363,9 +275,8 @@ pub fn main() -> Result<()> {
                  .|JF|-L|.J7LJJ77|F7|7F--|-|JL7-L7LFFJ-77-J|F-JFJ7JF|J|7|F-|7|-L|||F7.|L7-F|LJJ7-|7.|FLLF7LL-FFJ|.-L--7.LF||F-J.L|F7.L7F7.F7LLJ7J|F|-|J|L|L77
                  F-7|L77L..F-J7F---7J7||.|-F.LL7.|F7.JL|F7LF|-FF-LF7|-7-|.L--|...FL|L-JFF-J-.|.7F||JF||.FL7L.7||L7JJ-7FF-7|F|-|-LF--J7L7LL7JL||LF|F--LJ|-FJ|7
                  L|L--JJJ-LLJ-FF.JJLLJ7-L7-JLJJ|-.F7..-|LJ.FJ.|J..L7J.L-.--JL|.-FJ-L-LL.|.JJ-FJ.F|JLLJF-7J7JJJ|7JLJ..FL--JJ-77L7.|JLL-JL-L.LL-|7-L|J.LLL-L-|.";
-    let result = process(input)?;
-
-    println!("10.1: {}", result);
+    let (_, map) = mem::phase("parse", || parse(input).map_err(|e| e.to_owned()))?;
+    let result = mem::phase("solve", || solve(&map))?;
 
-    Ok(())
+    Ok(result.to_string())
 }
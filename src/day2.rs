@@ -0,0 +1,538 @@
+use crate::runner;
+use anyhow::{Context, Result};
+use pest::error::{Error, ErrorVariant, InputLocation};
+use pest::iterators::Pair;
+use pest::{Parser, Position};
+use pest_derive::Parser;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::ops::Range;
+use std::path::Path;
+
+#[derive(Parser)]
+#[grammar = "src/day2.pest"]
+pub struct GameParser;
+
+/// Byte range of a node in the input.
+pub type Span = Range<usize>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<Set>,
+    pub span: Span,
+}
+
+/// Cube counts by colour.
+pub type Cubes = BTreeMap<String, u32>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Set {
+    pub cubes: Cubes,
+    pub span: Span,
+}
+
+impl Game {
+    /// The fewest cubes of each colour the bag could have held for this game.
+    pub fn minimal_bag(&self) -> Bag {
+        let mut bag = Bag::default();
+
+        for set in self.sets.iter() {
+            bag.include(&set.cubes);
+        }
+
+        bag
+    }
+}
+
+/// How many cubes of each colour are in the bag, written like a set: `12 red, 13 green, 14 blue`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Bag {
+    pub cubes: Cubes,
+}
+
+/// A colour that a game shows more of than the bag holds.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Rejection {
+    pub colour: String,
+    pub shown: u32,
+    pub in_bag: u32,
+}
+
+impl Bag {
+    /// The bag from the puzzle text.
+    pub fn puzzle() -> Bag {
+        Bag::parse("12 red, 13 green, 14 blue").expect("a valid bag")
+    }
+
+    pub fn parse(spec: &str) -> Result<Bag> {
+        let set = GameParser::parse(Rule::bag, spec.trim())?
+            .flat_map(|bag| bag.into_inner())
+            .find(|pair| pair.as_rule() == Rule::set)
+            .map(Set::from_pair)
+            .expect("the bag rule holds a set")?;
+
+        Ok(Bag { cubes: set.cubes })
+    }
+
+    /// `--param bag=...` as a bag or a file holding one, or the puzzle's bag if not given.
+    pub fn from_param() -> Result<Bag> {
+        runner::param("bag")
+            .map(|spec| Bag::read(&spec))
+            .unwrap_or(Ok(Bag::puzzle()))
+    }
+
+    /// A bag, or the file holding one if `spec` looks like a path or names an existing file.
+    fn read(spec: &str) -> Result<Bag> {
+        if !spec.contains(std::path::is_separator) && !Path::new(spec).exists() {
+            return Bag::parse(spec);
+        }
+
+        let content = std::fs::read_to_string(spec)
+            .with_context(|| format!("Could not read the bag file {:?}", spec))?;
+
+        Bag::parse(&content).with_context(|| format!("In bag file {:?}", spec))
+    }
+
+    /// Grows the bag until it holds at least `cubes`.
+    fn include(&mut self, cubes: &Cubes) {
+        for (colour, count) in cubes {
+            let held = self.cubes.entry(colour.clone()).or_default();
+            *held = (*held).max(*count);
+        }
+    }
+
+    /// Every colour this game shows more of than the bag holds, including colours the bag does
+    /// not have at all. Empty if the game is feasible.
+    pub fn rejections(&self, game: &Game) -> Vec<Rejection> {
+        game.minimal_bag()
+            .cubes
+            .into_iter()
+            .map(|(colour, shown)| {
+                let in_bag = self.cubes.get(&colour).copied().unwrap_or_default();
+
+                Rejection {
+                    colour,
+                    shown,
+                    in_bag,
+                }
+            })
+            .filter(|rejection| rejection.shown > rejection.in_bag)
+            .collect()
+    }
+
+    pub fn allows(&self, game: &Game) -> bool {
+        self.rejections(game).is_empty()
+    }
+}
+
+impl Display for Bag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cubes = self
+            .cubes
+            .iter()
+            .map(|(colour, count)| format!("{} {}", count, colour))
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", cubes.join(", "))
+    }
+}
+
+/// The smallest bag that every game is feasible with.
+pub fn minimal_bag(games: &[Game]) -> Bag {
+    let mut bag = Bag::default();
+
+    for game in games {
+        bag.include(&game.minimal_bag().cubes);
+    }
+
+    bag
+}
+
+/// What `--explain` shows for a bag: the games it allows, why it rejects the rest, and the
+/// smallest bag that would allow them all.
+#[derive(Debug, Serialize)]
+pub struct Feasibility {
+    pub bag: Bag,
+    pub feasible: Vec<u32>,
+    pub rejected: Vec<(u32, Vec<Rejection>)>,
+    pub minimal_bag: Bag,
+}
+
+impl Feasibility {
+    pub fn new(bag: &Bag, games: &[Game]) -> Feasibility {
+        let mut feasibility = Feasibility {
+            bag: bag.clone(),
+            feasible: vec![],
+            rejected: vec![],
+            minimal_bag: minimal_bag(games),
+        };
+
+        for game in games {
+            match bag.rejections(game) {
+                rejections if rejections.is_empty() => feasibility.feasible.push(game.id),
+                rejections => feasibility.rejected.push((game.id, rejections)),
+            }
+        }
+
+        feasibility
+    }
+}
+
+impl Display for Feasibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "bag: {}", self.bag)?;
+        writeln!(
+            f,
+            "feasible games: {}",
+            self.feasible
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+
+        for (id, rejections) in self.rejected.iter() {
+            let reasons = rejections
+                .iter()
+                .map(|r| format!("{} {} > {}", r.shown, r.colour, r.in_bag))
+                .collect::<Vec<_>>();
+
+            writeln!(f, "game {} rejected: {}", id, reasons.join(", "))?;
+        }
+
+        writeln!(f, "minimal bag for all games: {}", self.minimal_bag)
+    }
+}
+
+/// Builds an AST node from the pest pair of its rule.
+trait FromPair: Sized {
+    const RULE: Rule;
+
+    fn from_pair(pair: Pair<Rule>) -> Result<Self>;
+}
+
+/// Points at `span` in the input the way pest reports its own errors.
+fn error_at(span: pest::Span, message: String) -> anyhow::Error {
+    Error::<Rule>::new_from_span(ErrorVariant::CustomError { message }, span).into()
+}
+
+/// Every child of `pair` that has `T`'s rule, built into a `T`.
+fn children<T: FromPair>(pair: Pair<Rule>) -> Result<Vec<T>> {
+    pair.into_inner()
+        .filter(|child| child.as_rule() == T::RULE)
+        .map(T::from_pair)
+        .collect()
+}
+
+fn int(pair: Pair<Rule>) -> Result<u32> {
+    pair.as_str()
+        .parse()
+        .map_err(|e| error_at(pair.as_span(), format!("{}", e)))
+}
+
+impl FromPair for Set {
+    const RULE: Rule = Rule::set;
+
+    fn from_pair(pair: Pair<Rule>) -> Result<Set> {
+        let span = pair.as_span();
+        let mut set = Set {
+            cubes: Cubes::new(),
+            span: span.start()..span.end(),
+        };
+
+        for block in pair.into_inner() {
+            let block_span = block.as_span();
+            let mut parts = block.into_inner();
+            let (Some(count), Some(colour)) = (parts.next(), parts.next()) else {
+                unreachable!("the grammar puts a count and a colour in every color_block");
+            };
+            let count = int(count)?;
+
+            if set
+                .cubes
+                .insert(colour.as_str().to_string(), count)
+                .is_some()
+            {
+                return Err(error_at(
+                    block_span,
+                    format!("{} appears more than once in this set", colour.as_str()),
+                ));
+            }
+        }
+
+        Ok(set)
+    }
+}
+
+impl FromPair for Game {
+    const RULE: Rule = Rule::line;
+
+    fn from_pair(pair: Pair<Rule>) -> Result<Game> {
+        let span = pair.as_span();
+        let id = pair
+            .clone()
+            .into_inner()
+            .find(|child| child.as_rule() == Rule::id)
+            .and_then(|id| id.into_inner().next())
+            .map(int)
+            .expect("the grammar starts every line with an id")?;
+
+        Ok(Game {
+            id,
+            sets: children(pair)?,
+            span: span.start()..span.end(),
+        })
+    }
+}
+
+/// Explains why a junk line is not a game, by parsing it on its own and moving pest's error
+/// back to where the line sits in `input`.
+fn junk_error(input: &str, junk: Pair<Rule>) -> anyhow::Error {
+    let offset = junk.as_span().start();
+    let Err(error) = GameParser::parse(Rule::game, junk.as_str()) else {
+        return error_at(junk.as_span(), "could not parse this game".to_string());
+    };
+
+    let error = match error.location {
+        InputLocation::Pos(pos) => Position::new(input, offset + pos)
+            .map(|pos| Error::new_from_pos(error.variant.clone(), pos)),
+        InputLocation::Span((start, end)) => pest::Span::new(input, offset + start, offset + end)
+            .map(|span| Error::new_from_span(error.variant.clone(), span)),
+    };
+
+    error.expect("a position inside the junk line").into()
+}
+
+/// Parses every game it can. Lines that are not games come back as errors pointing into `input`.
+pub fn parse_lines(input: &str) -> (Vec<Game>, Vec<anyhow::Error>) {
+    let mut games = vec![];
+    let mut errors = vec![];
+    let file = GameParser::parse(Rule::file, input)
+        .expect("the file rule accepts any input")
+        .next()
+        .expect("the file rule always produces a pair");
+
+    for entry in file.into_inner() {
+        match entry.as_rule() {
+            Rule::line => match Game::from_pair(entry) {
+                Ok(game) => games.push(game),
+                Err(error) => errors.push(error),
+            },
+            Rule::junk => errors.push(junk_error(input, entry)),
+            _ => {}
+        }
+    }
+
+    (games, errors)
+}
+
+/// Like [`parse_lines`], but the first bad line fails the whole input.
+#[cfg(test)]
+pub fn parse(input: &str) -> Result<Vec<Game>> {
+    let (games, errors) = parse_lines(input);
+
+    match errors.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(games),
+    }
+}
+
+/// Parses what it can for a day's `main`, and reports the lines it had to skip on stderr.
+pub fn parse_or_skip(input: &str) -> Vec<Game> {
+    let (games, errors) = parse_lines(input);
+
+    for error in errors {
+        eprintln!("Skipping a line that is not a game:\n{}", error);
+    }
+
+    games
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cubes(counts: &[(&str, u32)]) -> Cubes {
+        counts.iter().map(|(c, n)| (c.to_string(), *n)).collect()
+    }
+
+    #[test]
+    fn test_parsing() -> Result<()> {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+";
+
+        assert_eq!(
+            parse(input)?,
+            vec![
+                Game {
+                    id: 1,
+                    sets: vec![
+                        Set {
+                            cubes: cubes(&[("blue", 3), ("red", 4)]),
+                            span: 8..21,
+                        },
+                        Set {
+                            cubes: cubes(&[("blue", 6), ("green", 2), ("red", 1)]),
+                            span: 23..45,
+                        },
+                        Set {
+                            cubes: cubes(&[("green", 2)]),
+                            span: 47..54,
+                        }
+                    ],
+                    span: 0..54,
+                },
+                Game {
+                    id: 2,
+                    sets: vec![
+                        Set {
+                            cubes: cubes(&[("blue", 1), ("green", 2)]),
+                            span: 63..78,
+                        },
+                        Set {
+                            cubes: cubes(&[("blue", 4), ("green", 3), ("red", 1)]),
+                            span: 80..102,
+                        },
+                        Set {
+                            cubes: cubes(&[("blue", 1), ("green", 1)]),
+                            span: 104..119,
+                        }
+                    ],
+                    span: 55..119,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn bag_queries() -> Result<()> {
+        let games = parse(
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+",
+        )?;
+        let bag = Bag::parse("14 blue, 12 red, 13 green\n")?;
+
+        assert_eq!(bag, Bag::puzzle());
+        assert!(Bag::parse("12 red, 13 green; 14 blue").is_err());
+        assert_eq!(Bag::read("12 red, 13 green, 14 blue")?, bag);
+        assert_eq!(
+            Bag::read("bags/puzle.txt").unwrap_err().to_string(),
+            "Could not read the bag file \"bags/puzle.txt\""
+        );
+        assert!(bag.allows(&games[0]));
+        assert_eq!(
+            bag.rejections(&games[2]),
+            vec![
+                Rejection {
+                    colour: "blue".to_string(),
+                    shown: 15,
+                    in_bag: 14
+                },
+                Rejection {
+                    colour: "red".to_string(),
+                    shown: 14,
+                    in_bag: 12
+                }
+            ]
+        );
+        assert_eq!(
+            minimal_bag(&games),
+            Bag {
+                cubes: cubes(&[("red", 20), ("green", 13), ("blue", 15)])
+            }
+        );
+        assert_eq!(
+            Feasibility::new(&bag, &games).to_string(),
+            "bag: 14 blue, 13 green, 12 red
+feasible games: 1
+game 3 rejected: 20 red > 12
+game 4 rejected: 15 blue > 14, 14 red > 12
+minimal bag for all games: 15 blue, 13 green, 20 red
+"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn any_colour() -> Result<()> {
+        let games = parse("Game 7: 2 cyan, 1 red; 4 magenta, 3 cyan\n")?;
+
+        assert_eq!(
+            games[0].minimal_bag(),
+            Bag {
+                cubes: cubes(&[("cyan", 3), ("magenta", 4), ("red", 1)])
+            }
+        );
+        assert_eq!(
+            Bag::puzzle().rejections(&games[0]),
+            vec![
+                Rejection {
+                    colour: "cyan".to_string(),
+                    shown: 3,
+                    in_bag: 0
+                },
+                Rejection {
+                    colour: "magenta".to_string(),
+                    shown: 4,
+                    in_bag: 0
+                }
+            ]
+        );
+        assert!(Bag::parse("3 cyan, 4 magenta, 1 red")?.allows(&games[0]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bad_lines_do_not_stop_the_rest() {
+        let input =
+            "Game 1: 3 blue\nGame 2: 4 blue, 1 red; purple\n\nGame 3 1 red\r\nGame 4: 2 green";
+        let (games, errors) = parse_lines(input);
+
+        assert_eq!(
+            games.iter().map(|g| g.id).collect::<Vec<_>>(),
+            vec![1, 4],
+            "the last line needs no newline"
+        );
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].to_string(),
+            " --> 2:24
+  |
+2 | Game 2: 4 blue, 1 red; purple
+  |                        ^---
+  |
+  = expected int"
+        );
+        assert!(errors[1].to_string().starts_with(" --> 4:1"));
+        assert!(errors[1].to_string().ends_with("expected id"));
+        assert!(parse(input).is_err());
+    }
+
+    #[test]
+    fn errors_point_at_the_input() {
+        let error = parse("Game 1: 3 blue\nGame 2: 1 red, 2 green, 4 red\n").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            " --> 2:25
+  |
+2 | Game 2: 1 red, 2 green, 4 red
+  |                         ^---^
+  |
+  = red appears more than once in this set"
+        );
+
+        let error = parse("Game 99999999999: 3 blue\n").unwrap_err();
+        assert!(error.to_string().contains("^---------^"));
+    }
+}
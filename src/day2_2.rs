@@ -1,154 +1,26 @@
-use anyhow::{bail, Result};
-use pest::Parser;
-use pest_derive::Parser;
-
-#[derive(Parser)]
-#[grammar = "src/day2.pest"]
-pub struct GameParser;
-
-#[derive(Debug, PartialEq, PartialOrd, Ord, Eq)]
-struct Game {
-    id: u32,
-    sets: Vec<Box<Set>>,
-}
-
-#[derive(Debug, PartialEq, PartialOrd, Ord, Eq)]
-struct Set {
-    red: u32,
-    green: u32,
-    blue: u32,
-}
-
-fn parse(content: String) -> Result<Vec<Game>> {
-    let parsed = GameParser::parse(Rule::file, &content)?;
-    let mut games = vec![];
-
-    for file in parsed {
-        match file.as_rule() {
-            Rule::file => {
-                for line in file.into_inner() {
-                    match line.as_rule() {
-                        Rule::line => {
-                            let mut game = Game {
-                                id: 0,
-                                sets: Vec::new(),
-                            };
-
-                            for block in line.into_inner() {
-                                match block.as_rule() {
-                                    Rule::id => {
-                                        for part in block.into_inner() {
-                                            match part.as_rule() {
-                                                Rule::int => {
-                                                    game.id = part.as_span().as_str().parse()?
-                                                }
-                                                x => bail!("expected int, got {:?}", x),
-                                            }
-                                        }
-                                    }
-                                    Rule::set => {
-                                        let mut set = Set {
-                                            red: 0,
-                                            green: 0,
-                                            blue: 0,
-                                        };
-
-                                        for part in block.into_inner() {
-                                            match part.as_rule() {
-                                                Rule::color_block => {
-                                                    let mut seen_value: Option<u32> = None;
-
-                                                    for color_or_int in part.into_inner() {
-                                                        match color_or_int.as_rule() {
-                                                            Rule::color => {
-                                                                let value = seen_value
-                                                                    .expect("Missing value");
-
-                                                                match color_or_int
-                                                                    .as_span()
-                                                                    .as_str()
-                                                                {
-                                                                    "red" => {
-                                                                        set.red += value;
-                                                                    }
-                                                                    "green" => {
-                                                                        set.green += value;
-                                                                    }
-                                                                    "blue" => {
-                                                                        set.blue += value;
-                                                                    },
-                                                x => bail!("Expected \"red\", \"green\" or \"blue\", got \"{}\"", x),
-                                                                }
-
-                                                                seen_value = None;
-                                                            }
-                                                            Rule::int => {
-                                                                seen_value = Some(
-                                                                    color_or_int
-                                                                        .as_span()
-                                                                        .as_str()
-                                                                        .parse()?,
-                                                                );
-                                                            }
-                                                            x => bail!(
-                                                                "Expected color or int, got {:?}",
-                                                                x
-                                                            ),
-                                                        }
-                                                    }
-                                                }
-                                                x => bail!("Expected color_block, got {:?}", x),
-                                            }
-                                        }
-
-                                        game.sets.push(Box::new(set));
-                                    }
-                                    x => bail!("Expected set or ID, got {:?}", x),
-                                }
-                            }
-
-                            games.push(game);
-                        }
-                        Rule::EOI => return Ok(games),
-                        x => bail!("Expected line, got {:?}", x),
-                    }
-                }
-            }
-            x => bail!("Expected file or EOI, got {:?}", x),
-        }
+#[cfg(test)]
+use crate::day2::parse;
+use crate::day2::{parse_or_skip, Game};
+use crate::mem;
+use crate::num::{self, Int};
+use anyhow::Result;
+
+fn sum<T: Int>(games: &[Game]) -> Result<T> {
+    let mut total = T::ZERO;
+
+    for game in games {
+        let cubes = game.minimal_bag().cubes.into_values();
+        let power = num::product(
+            cubes
+                .map(|count| num::cast(count, "cube count"))
+                .collect::<Result<Vec<T>>>()?,
+            "power of a game",
+        )?;
+
+        total = num::add(total, power, "sum of powers")?;
     }
 
-    bail!("")
-}
-
-fn sum(games: &[Game]) -> u32 {
-    games
-        .iter()
-        .map(|g| {
-            let max = g.sets.iter().fold(
-                Set {
-                    red: 1,
-                    green: 1,
-                    blue: 1,
-                },
-                |mut acc, curr| {
-                    if curr.red > acc.red {
-                        acc.red = curr.red;
-                    }
-                    if curr.green > acc.green {
-                        acc.green = curr.green;
-                    }
-                    if curr.blue > acc.blue {
-                        acc.blue = curr.blue;
-                    }
-
-                    acc
-                },
-            );
-
-            max.red * max.green * max.blue
-        })
-        .sum()
+    Ok(total)
 }
 
 #[cfg(test)]
@@ -156,7 +28,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parsing() -> Result<()> {
+    fn test_summing() -> Result<()> {
         let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
@@ -164,215 +36,22 @@ Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
 ";
 
-        assert_eq!(
-            parse(input.to_string())?,
-            vec![
-                Game {
-                    id: 1,
-                    sets: vec![
-                        Box::new(Set {
-                            blue: 3,
-                            red: 4,
-                            green: 0
-                        }),
-                        Box::new(Set {
-                            red: 1,
-                            green: 2,
-                            blue: 6,
-                        }),
-                        Box::new(Set {
-                            red: 0,
-                            green: 2,
-                            blue: 0,
-                        })
-                    ]
-                },
-                Game {
-                    id: 2,
-                    sets: vec![
-                        Box::new(Set {
-                            blue: 1,
-                            green: 2,
-                            red: 0,
-                        }),
-                        Box::new(Set {
-                            red: 1,
-                            green: 3,
-                            blue: 4,
-                        }),
-                        Box::new(Set {
-                            red: 0,
-                            green: 1,
-                            blue: 1,
-                        })
-                    ]
-                },
-                Game {
-                    id: 3,
-                    sets: vec![
-                        Box::new(Set {
-                            green: 8,
-                            blue: 6,
-                            red: 20,
-                        }),
-                        Box::new(Set {
-                            blue: 5,
-                            red: 4,
-                            green: 13,
-                        }),
-                        Box::new(Set {
-                            red: 1,
-                            green: 5,
-                            blue: 0,
-                        })
-                    ]
-                },
-                Game {
-                    id: 4,
-                    sets: vec![
-                        Box::new(Set {
-                            green: 1,
-                            red: 3,
-                            blue: 6,
-                        }),
-                        Box::new(Set {
-                            green: 3,
-                            red: 6,
-                            blue: 0,
-                        }),
-                        Box::new(Set {
-                            red: 14,
-                            blue: 15,
-                            green: 3,
-                        })
-                    ]
-                },
-                Game {
-                    id: 5,
-                    sets: vec![
-                        Box::new(Set {
-                            red: 6,
-                            blue: 1,
-                            green: 3,
-                        }),
-                        Box::new(Set {
-                            blue: 2,
-                            red: 1,
-                            green: 2,
-                        }),
-                    ]
-                }
-            ]
-        );
+        assert_eq!(sum::<u32>(&parse(input)?)?, 2286);
 
         Ok(())
     }
 
     #[test]
-    fn test_summing() {
-        let input = vec![
-            Game {
-                id: 1,
-                sets: vec![
-                    Box::new(Set {
-                        blue: 3,
-                        red: 4,
-                        green: 0,
-                    }),
-                    Box::new(Set {
-                        red: 1,
-                        green: 2,
-                        blue: 6,
-                    }),
-                    Box::new(Set {
-                        red: 0,
-                        green: 2,
-                        blue: 0,
-                    }),
-                ],
-            },
-            Game {
-                id: 2,
-                sets: vec![
-                    Box::new(Set {
-                        blue: 1,
-                        green: 2,
-                        red: 0,
-                    }),
-                    Box::new(Set {
-                        red: 1,
-                        green: 3,
-                        blue: 4,
-                    }),
-                    Box::new(Set {
-                        red: 0,
-                        green: 1,
-                        blue: 1,
-                    }),
-                ],
-            },
-            Game {
-                id: 3,
-                sets: vec![
-                    Box::new(Set {
-                        green: 8,
-                        blue: 6,
-                        red: 20,
-                    }),
-                    Box::new(Set {
-                        blue: 5,
-                        red: 4,
-                        green: 13,
-                    }),
-                    Box::new(Set {
-                        red: 1,
-                        green: 5,
-                        blue: 0,
-                    }),
-                ],
-            },
-            Game {
-                id: 4,
-                sets: vec![
-                    Box::new(Set {
-                        green: 1,
-                        red: 3,
-                        blue: 6,
-                    }),
-                    Box::new(Set {
-                        green: 3,
-                        red: 6,
-                        blue: 0,
-                    }),
-                    Box::new(Set {
-                        red: 14,
-                        blue: 15,
-                        green: 3,
-                    }),
-                ],
-            },
-            Game {
-                id: 5,
-                sets: vec![
-                    Box::new(Set {
-                        red: 6,
-                        blue: 1,
-                        green: 3,
-                    }),
-                    Box::new(Set {
-                        blue: 2,
-                        red: 1,
-                        green: 2,
-                    }),
-                ],
-            },
-        ];
+    fn power_over_any_colours() -> Result<()> {
+        let input = "Game 1: 2 cyan, 3 red; 4 cyan, 5 magenta\nGame 2: 7 green\n";
+
+        assert_eq!(sum::<u32>(&parse(input)?)?, 4 * 5 * 3 + 7);
 
-        assert_eq!(sum(&input), 2286);
+        Ok(())
     }
 }
 
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let content = "Game 1: 7 red, 14 blue; 2 blue, 3 red, 3 green; 4 green, 12 blue, 15 red; 3 green, 12 blue, 3 red; 11 red, 2 green
 Game 2: 16 blue, 9 red, 5 green; 8 red; 8 blue, 5 green, 12 red; 11 blue, 8 green, 17 red
 Game 3: 8 green, 1 blue, 7 red; 12 red, 6 blue, 9 green; 2 blue, 1 red, 14 green; 9 green, 4 red; 2 red, 1 blue, 8 green
@@ -474,10 +153,10 @@ Game 98: 4 blue, 10 red, 8 green; 2 red, 3 green; 5 red, 4 blue, 10 green
 Game 99: 9 blue, 12 red; 9 blue, 11 red, 13 green; 9 blue, 1 red, 13 green; 4 blue, 12 green; 10 blue, 17 red, 8 green
 Game 100: 8 red, 3 green; 4 green, 1 blue, 15 red; 10 red, 8 green, 1 blue
 ";
-    let parsed = parse(content.to_string())?;
-    let result = sum(&parsed);
-
-    println!("2.2: {}", result);
+    let parsed = mem::phase("parse", || parse_or_skip(content));
+    let result = mem::phase("solve", || {
+        num::widen(|| sum::<u32>(&parsed), || sum::<u128>(&parsed))
+    })?;
 
-    Ok(())
+    Ok(result)
 }
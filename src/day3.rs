@@ -0,0 +1,592 @@
+use crate::num::{self, Int};
+use crate::runner;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::fmt::Display;
+use std::ops::Range;
+
+/// Which number in [`Schematic::numbers`] is meant. Numbers are told apart by this, never by
+/// value, so two `12`s around one gear are two neighbours.
+pub type NumberId = usize;
+
+/// A number in the schematic, and the columns its digits take up in `row`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    pub cols: Range<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub char: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+/// How many numbers a symbol must touch to be picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Degree {
+    Exactly(usize),
+    #[cfg_attr(not(test), allow(dead_code))]
+    AtLeast(usize),
+}
+
+impl Degree {
+    fn matches(&self, count: usize) -> bool {
+        match self {
+            Degree::Exactly(n) => count == *n,
+            Degree::AtLeast(n) => count >= *n,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Diagonal steps count as one, so radius 1 is the 8 surrounding cells.
+    Chebyshev,
+    /// Only straight steps count, so radius 1 is the 4 cells sharing an edge.
+    Manhattan,
+}
+
+/// Which cells count as touching a digit, set with `--param neighbourhood=...` as `4`, `8`,
+/// `chebyshev:N` or `manhattan:N`, with `,wrap` to wrap around the edges of the schematic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Neighbourhood {
+    pub metric: Metric,
+    pub radius: usize,
+    pub wrap: bool,
+}
+
+impl Default for Neighbourhood {
+    fn default() -> Self {
+        Neighbourhood {
+            metric: Metric::Chebyshev,
+            radius: 1,
+            wrap: false,
+        }
+    }
+}
+
+impl Neighbourhood {
+    pub fn parse(spec: &str) -> Result<Neighbourhood> {
+        let (shape, wrap) = match spec.strip_suffix(",wrap") {
+            Some(shape) => (shape, true),
+            None => (spec, false),
+        };
+        let (metric, radius) = match shape.split_once(':') {
+            None if shape == "4" => (Metric::Manhattan, 1),
+            None if shape == "8" => (Metric::Chebyshev, 1),
+            Some(("chebyshev", radius)) => (Metric::Chebyshev, radius.parse()?),
+            Some(("manhattan", radius)) => (Metric::Manhattan, radius.parse()?),
+            _ => bail!(
+                "Expected a neighbourhood of 4, 8, chebyshev:N or manhattan:N, got {:?}",
+                spec
+            ),
+        };
+
+        if radius == 0 {
+            bail!(
+                "A neighbourhood needs a radius of at least 1, got {:?}",
+                spec
+            );
+        }
+
+        Ok(Neighbourhood {
+            metric,
+            radius,
+            wrap,
+        })
+    }
+
+    pub fn from_param() -> Result<Neighbourhood> {
+        runner::param("neighbourhood")
+            .map(|spec| Neighbourhood::parse(&spec))
+            .unwrap_or(Ok(Neighbourhood::default()))
+    }
+
+    /// Whether a cell `rows` rows and `cols` columns away counts as touching.
+    fn reaches(&self, rows: usize, cols: usize) -> bool {
+        let distance = match self.metric {
+            Metric::Chebyshev => rows.max(cols),
+            Metric::Manhattan => rows.saturating_add(cols),
+        };
+
+        distance <= self.radius
+    }
+
+    /// How far apart two positions `size` wide are, the short way round if the edges wrap.
+    fn gap(&self, a: usize, b: usize, size: usize) -> usize {
+        let gap = a.abs_diff(b);
+
+        if self.wrap {
+            gap.min(size - gap)
+        } else {
+            gap
+        }
+    }
+
+    /// How many columns separate `col` from the nearest column in `cols`.
+    fn gap_to_span(&self, col: usize, cols: &Range<usize>, width: usize) -> usize {
+        if cols.contains(&col) {
+            return 0;
+        }
+
+        self.gap(col, cols.start, width)
+            .min(self.gap(col, cols.end - 1, width))
+    }
+}
+
+/// The numbers and symbols of an engine schematic, and which of them touch. Columns count
+/// characters, not bytes.
+#[derive(Debug)]
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    /// For every symbol, the numbers it touches.
+    symbol_numbers: Vec<Vec<NumberId>>,
+    /// For every number, the indices of the symbols it touches.
+    number_symbols: Vec<Vec<usize>>,
+    /// How many characters each row has.
+    widths: Vec<usize>,
+}
+
+impl Schematic {
+    /// Parses with the usual 8-connected neighbourhood.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn parse(input: &str) -> Result<Schematic> {
+        Schematic::parse_with(input, &Neighbourhood::default())
+    }
+
+    pub fn parse_with(input: &str, neighbourhood: &Neighbourhood) -> Result<Schematic> {
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+        let mut widths = vec![];
+
+        for (row, line) in input.lines().enumerate() {
+            let mut number: Option<(u32, usize)> = None;
+            let mut width = 0;
+
+            for (col, char) in line.chars().enumerate() {
+                width = col + 1;
+
+                if let Some(digit) = char.to_digit(10) {
+                    let (value, start) = number.unwrap_or((0, col));
+                    let tens = num::mul(value, 10, "part number")?;
+
+                    number = Some((num::add(tens, digit, "part number")?, start));
+                    continue;
+                }
+
+                if let Some((value, start)) = number.take() {
+                    numbers.push(Number {
+                        value,
+                        row,
+                        cols: start..col,
+                    });
+                }
+
+                if char != '.' {
+                    symbols.push(Symbol { char, row, col });
+                }
+            }
+
+            if let Some((value, start)) = number {
+                numbers.push(Number {
+                    value,
+                    row,
+                    cols: start..width,
+                });
+            }
+
+            widths.push(width);
+        }
+
+        Ok(Schematic::new(numbers, symbols, widths, neighbourhood))
+    }
+
+    fn new(
+        numbers: Vec<Number>,
+        symbols: Vec<Symbol>,
+        widths: Vec<usize>,
+        neighbourhood: &Neighbourhood,
+    ) -> Schematic {
+        let mut symbol_numbers = vec![vec![]; symbols.len()];
+        let mut number_symbols = vec![vec![]; numbers.len()];
+
+        let (height, width) = (widths.len(), widths.iter().copied().max().unwrap_or(0));
+
+        // Measured from each symbol to the whole span of a number, so a wide radius costs no
+        // more than a narrow one.
+        for (id, number) in numbers.iter().enumerate() {
+            for (index, symbol) in symbols.iter().enumerate() {
+                let rows = neighbourhood.gap(number.row, symbol.row, height);
+                let cols = neighbourhood.gap_to_span(symbol.col, &number.cols, width);
+
+                if neighbourhood.reaches(rows, cols) {
+                    symbol_numbers[index].push(id);
+                    number_symbols[id].push(index);
+                }
+            }
+        }
+
+        Schematic {
+            numbers,
+            symbols,
+            symbol_numbers,
+            number_symbols,
+            widths,
+        }
+    }
+
+    /// Numbers touching at least one symbol in `class`, each once.
+    pub fn numbers_adjacent_to<'a>(
+        &'a self,
+        class: impl Fn(char) -> bool + 'a,
+    ) -> impl Iterator<Item = &'a Number> + 'a {
+        self.numbers
+            .iter()
+            .zip(self.number_symbols.iter())
+            .filter(move |(_, symbols)| symbols.iter().any(|&s| class(self.symbols[s].char)))
+            .map(|(number, _)| number)
+    }
+
+    /// Indices of the symbols in `class` that touch as many numbers as `degree` asks for.
+    pub fn symbols_where<'a>(
+        &'a self,
+        class: impl Fn(char) -> bool + 'a,
+        degree: Degree,
+    ) -> impl Iterator<Item = usize> + 'a {
+        (0..self.symbols.len()).filter(move |&index| {
+            class(self.symbols[index].char) && degree.matches(self.symbol_numbers[index].len())
+        })
+    }
+
+    /// The numbers touching a symbol, each once even if it touches with several digits.
+    pub fn neighbours(&self, symbol: usize) -> impl Iterator<Item = &Number> {
+        self.symbol_numbers[symbol]
+            .iter()
+            .map(|&id| &self.numbers[id])
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn sum_around<T: Int>(&self, symbol: usize) -> Result<T> {
+        let values = self
+            .neighbours(symbol)
+            .map(|n| num::cast(n.value, "part number"))
+            .collect::<Result<Vec<T>>>()?;
+
+        num::sum(values, "sum around a symbol")
+    }
+
+    pub fn product_around<T: Int>(&self, symbol: usize) -> Result<T> {
+        let values = self
+            .neighbours(symbol)
+            .map(|n| num::cast(n.value, "part number"))
+            .collect::<Result<Vec<T>>>()?;
+
+        num::product(values, "product around a symbol")
+    }
+}
+
+/// How `--explain` marks up the schematic, picked with `--param render=...`. Markup by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Ansi,
+    /// Brackets around marked cells, for terminals and files without colour.
+    Markup,
+}
+
+impl Style {
+    pub fn from_param() -> Result<Option<Style>> {
+        match runner::param("render").as_deref() {
+            None => Ok(None),
+            Some("ansi") => Ok(Some(Style::Ansi)),
+            Some("markup") => Ok(Some(Style::Markup)),
+            Some(x) => bail!("Expected render=ansi or render=markup, got {:?}", x),
+        }
+    }
+
+    fn paint(&self, mark: Mark, text: &str) -> String {
+        match self {
+            Style::Ansi => {
+                let code = match mark {
+                    Mark::Part => "32",
+                    Mark::Ignored => "2",
+                    Mark::Gear => "1;33",
+                    Mark::Crowded => "1;31",
+                    Mark::Symbol => "36",
+                };
+
+                format!("\x1b[{}m{}\x1b[0m", code, text)
+            }
+            Style::Markup => match mark {
+                Mark::Part => format!("[{}]", text),
+                Mark::Ignored => format!("({})", text),
+                Mark::Gear => format!("{{{}}}", text),
+                Mark::Crowded => format!("<{}>", text),
+                Mark::Symbol => text.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Mark {
+    /// A number touching a symbol.
+    Part,
+    Ignored,
+    /// A `*` touching exactly two numbers.
+    Gear,
+    /// A symbol touching more than two numbers.
+    Crowded,
+    Symbol,
+}
+
+/// The schematic drawn with every number and symbol marked, gear ratios beside their rows, and a
+/// legend and totals below.
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct Rendering(String);
+
+impl Display for Rendering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Schematic {
+    fn mark(&self, symbol: usize) -> Mark {
+        match (self.symbols[symbol].char, self.symbol_numbers[symbol].len()) {
+            (_, n) if n > 2 => Mark::Crowded,
+            ('*', 2) => Mark::Gear,
+            _ => Mark::Symbol,
+        }
+    }
+
+    pub fn render(&self, style: Style) -> Result<Rendering> {
+        let mut out = String::new();
+        let mut part_sum = 0u64;
+        let mut ignored = 0;
+        let mut ratio_sum = 0u64;
+        let mut gears = 0;
+        let mut numbers = self.numbers.iter().enumerate().peekable();
+        let mut symbols = self.symbols.iter().enumerate().peekable();
+
+        for (row, &width) in self.widths.iter().enumerate() {
+            let mut ratios = vec![];
+            let mut col = 0;
+
+            while col < width {
+                if let Some((id, number)) =
+                    numbers.next_if(|(_, n)| (n.row, n.cols.start) == (row, col))
+                {
+                    let text = format!("{:0width$}", number.value, width = number.cols.len());
+
+                    if self.number_symbols[id].is_empty() {
+                        ignored += 1;
+                        out.push_str(&style.paint(Mark::Ignored, &text));
+                    } else {
+                        part_sum = num::add(part_sum, number.value as u64, "sum of part numbers")?;
+                        out.push_str(&style.paint(Mark::Part, &text));
+                    }
+
+                    col = number.cols.end;
+                } else if let Some((index, symbol)) =
+                    symbols.next_if(|(_, s)| (s.row, s.col) == (row, col))
+                {
+                    let mark = self.mark(index);
+
+                    if let Mark::Gear = mark {
+                        let values = self.neighbours(index).map(|n| n.value.to_string());
+                        let ratio = self.product_around::<u64>(index)?;
+
+                        gears += 1;
+                        ratio_sum = num::add(ratio_sum, ratio, "sum of gear ratios")?;
+                        ratios.push(format!(
+                            "{} = {}",
+                            values.collect::<Vec<_>>().join(" * "),
+                            ratio
+                        ));
+                    }
+
+                    out.push_str(&style.paint(mark, &symbol.char.to_string()));
+                    col += 1;
+                } else {
+                    out.push('.');
+                    col += 1;
+                }
+            }
+
+            if !ratios.is_empty() {
+                out.push_str("  ");
+                out.push_str(&style.paint(Mark::Gear, &ratios.join(", ")));
+            }
+
+            out.push('\n');
+        }
+
+        let legend = [
+            (Mark::Part, "part number"),
+            (Mark::Ignored, "ignored number"),
+            (Mark::Gear, "gear"),
+            (Mark::Crowded, "symbol touching more than two numbers"),
+        ];
+
+        out.push('\n');
+        for (mark, meaning) in legend {
+            out.push_str(&format!("{} {}\n", style.paint(mark, "n"), meaning));
+        }
+
+        out.push_str(&format!(
+            "{} part numbers sum to {}, {} numbers ignored, {} gears with ratios summing to {}\n",
+            self.numbers.len() - ignored,
+            part_sum,
+            ignored,
+            gears,
+            ratio_sum
+        ));
+
+        Ok(Rendering(out))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn parsing() -> Result<()> {
+        let schematic = Schematic::parse("é.012\n..*#.\n.3...")?;
+
+        assert_eq!(
+            schematic.numbers,
+            vec![
+                Number {
+                    value: 12,
+                    row: 0,
+                    cols: 2..5
+                },
+                Number {
+                    value: 3,
+                    row: 2,
+                    cols: 1..2
+                }
+            ]
+        );
+        assert_eq!(
+            schematic.symbols.iter().map(|s| s.char).collect::<String>(),
+            "é*#"
+        );
+        assert_eq!(schematic.symbol_numbers, vec![vec![], vec![0, 1], vec![0]]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn equal_values_are_different_numbers() -> Result<()> {
+        let schematic = Schematic::parse("12*12\n.....\n7.7..\n.*...")?;
+
+        assert_eq!(schematic.symbol_numbers, vec![vec![0, 1], vec![2, 3]]);
+        assert_eq!(schematic.product_around::<u32>(0)?, 144);
+        assert_eq!(schematic.product_around::<u32>(1)?, 49);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rendering() -> Result<()> {
+        let schematic = Schematic::parse("467..114..\n...*......\n..35.*....\n1.2*3..\n...4.")?;
+
+        assert_eq!(
+            schematic.render(Style::Markup)?.to_string(),
+            "[467]..(114)..
+...{*}......  {467 * 35 = 16345}
+..[35].*....
+(1).[2]<*>[3]..
+...[4].
+
+[n] part number
+(n) ignored number
+{n} gear
+<n> symbol touching more than two numbers
+5 part numbers sum to 511, 2 numbers ignored, 1 gears with ratios summing to 16345
+"
+        );
+        assert!(schematic
+            .render(Style::Ansi)?
+            .to_string()
+            .starts_with("\x1b[32m467\x1b[0m..\x1b[2m114\x1b[0m"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn neighbourhoods() -> Result<()> {
+        let touching = |input: &str, spec: &str| -> Result<usize> {
+            let schematic = Schematic::parse_with(input, &Neighbourhood::parse(spec)?)?;
+
+            Ok(schematic.numbers_adjacent_to(|_| true).count())
+        };
+
+        assert_eq!(touching("12\n..*", "8")?, 1);
+        assert_eq!(touching("12\n..*", "4")?, 0);
+        assert_eq!(touching("12\n*..", "4")?, 1);
+        assert_eq!(touching("1.*", "manhattan:2")?, 1);
+        assert_eq!(touching("1..\n..*", "manhattan:2")?, 0);
+        assert_eq!(touching("1..\n..*", "chebyshev:2")?, 1);
+        assert_eq!(touching("*..1", "4")?, 0);
+        assert_eq!(touching("*..1", "4,wrap")?, 1);
+        assert_eq!(touching("1\n.\n*", "4,wrap")?, 1);
+        assert!(Neighbourhood::parse("6").is_err());
+        assert!(Neighbourhood::parse("manhattan:x").is_err());
+        assert!(Neighbourhood::parse("chebyshev:0").is_err());
+        assert_eq!(touching("1..\n..*", "chebyshev:99999999999")?, 1);
+        assert_eq!(touching("1.......*", "manhattan:18446744073709551615")?, 1);
+        assert_eq!(touching("1.....*.", "4,wrap")?, 0);
+        assert_eq!(touching("1.....*.", "manhattan:2,wrap")?, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn queries() -> Result<()> {
+        let schematic = Schematic::parse(EXAMPLE)?;
+
+        let not_parts = schematic
+            .numbers
+            .iter()
+            .filter(|n| !schematic.numbers_adjacent_to(|_| true).any(|p| p == *n))
+            .map(|n| n.value)
+            .collect::<Vec<_>>();
+        assert_eq!(not_parts, vec![114, 58]);
+
+        let near_stars = schematic.numbers_adjacent_to(|c| c == '*').count();
+        assert_eq!(near_stars, 5);
+
+        let lonely = schematic
+            .symbols_where(|c| c == '*', Degree::Exactly(1))
+            .map(|s| schematic.sum_around::<u32>(s))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(lonely, vec![617]);
+        assert_eq!(
+            schematic
+                .symbols_where(|_| true, Degree::AtLeast(2))
+                .count(),
+            2
+        );
+
+        Ok(())
+    }
+}
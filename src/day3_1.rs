@@ -1,74 +1,21 @@
+use crate::day3::{Neighbourhood, Schematic, Style};
+use crate::mem;
+use crate::num::{self, Int};
+use crate::runner;
 use anyhow::Result;
-use std::collections::HashSet;
 
-#[derive(Debug, Hash, PartialEq, Eq)]
-struct Point {
-    row: usize,
-    col: usize,
-}
+fn solve<T: Int>(schematic: &Schematic) -> Result<T> {
+    let parts = schematic
+        .numbers_adjacent_to(|_| true)
+        .map(|number| num::cast(number.value, "part number"))
+        .collect::<Result<Vec<T>>>()?;
 
-#[derive(Debug)]
-struct Number {
-    value: u32,
-    row: usize,
-    col: usize,
+    num::sum(parts, "sum of part numbers")
 }
 
-fn process(input: &str) -> u32 {
-    let mut numbers = vec![];
-    let mut symbols = HashSet::new();
-
-    for (row, line) in input.lines().into_iter().enumerate() {
-        let mut number: Option<u32> = None;
-
-        for (col, char) in line.chars().enumerate() {
-            if let Some(digit) = char.to_digit(10) {
-                number = Some(number.unwrap_or_default() * 10 + digit);
-            } else {
-                if let Some(value) = number {
-                    numbers.push(Number { value, row, col });
-                    number = None;
-                }
-
-                if char != '.' {
-                    symbols.insert(Point { row, col });
-                }
-            }
-        }
-
-        if let Some(value) = number {
-            numbers.push(Number {
-                value,
-                row,
-                col: line.len(),
-            });
-        }
-    }
-
-    numbers
-        .iter()
-        .filter_map(|n| {
-            let digits = (n.value.checked_ilog10().unwrap_or_default() + 1) as usize;
-
-            for rel_col in 0..digits + 2 {
-                for rel_row in 0..3 {
-                    let offset_col = rel_col + n.col;
-                    let offset_row = rel_row + n.row;
-
-                    if offset_col > digits && offset_row > 0 {
-                        if symbols.contains(&Point {
-                            row: offset_row - 1,
-                            col: offset_col - digits - 1,
-                        }) {
-                            return Some(n.value);
-                        }
-                    }
-                }
-            }
-
-            None
-        })
-        .sum()
+#[cfg(test)]
+fn process<T: Int>(input: &str) -> Result<T> {
+    solve(&Schematic::parse(input)?)
 }
 
 #[cfg(test)]
@@ -76,7 +23,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test() {
+    fn test() -> Result<()> {
         let input = "467..114..
 ...*......
 ..35..633.
@@ -88,23 +35,35 @@ mod tests {
 ...$.*....
 .664.598..";
 
-        assert_eq!(process(input), 4361);
+        assert_eq!(process::<u32>(input)?, 4361);
+
+        Ok(())
     }
 
     #[test]
-    fn literal_edge_case() {
+    fn literal_edge_case() -> Result<()> {
         assert_eq!(
-            process(
+            process::<u32>(
                 "1......1
 .....!10
 1......1"
-            ),
+            )?,
             10
         );
+
+        Ok(())
+    }
+
+    #[test]
+    fn non_ascii_noise() -> Result<()> {
+        assert_eq!(process::<u32>("ü·.12\n..#...")?, 12);
+        assert_eq!(process::<u32>("€..4.\n.二..5\n....é")?, 5);
+
+        Ok(())
     }
 }
 
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "....937..........309.............191..............745.................913......................................256................891.......
 ................*..................-..113.......3*..............219.........495%................40....97.......*.......670.60..../..........
 ......919..928..511......................................%......#...&...................................*.................*.................
@@ -245,9 +204,18 @@ pub fn main() -> Result<()> {
 548...............642...393*469.@.......$....*......................172.........@......860..........170......15.............-.....*.........
 ...@...........*.....*...........155..........998.........657.851-.....*...............................*.....&.........972.751.249..........
 ......898...561.186...207....270.....................................968...231..181..................324.........696........................";
-    let sum = process(&input);
+    let neighbourhood = Neighbourhood::from_param()?;
+    let schematic = mem::phase("parse", || Schematic::parse_with(input, &neighbourhood))?;
+
+    let style = Style::from_param()?;
+
+    if runner::explaining() {
+        runner::explain(&schematic.render(style.unwrap_or(Style::Markup))?);
+    }
 
-    println!("3.1: {}", sum);
+    let sum = mem::phase("solve", || {
+        num::widen(|| solve::<u32>(&schematic), || solve::<u128>(&schematic))
+    })?;
 
-    Ok(())
+    Ok(sum)
 }
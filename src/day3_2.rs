@@ -1,109 +1,21 @@
+use crate::day3::{Degree, Neighbourhood, Schematic, Style};
+use crate::mem;
+use crate::num::{self, Int};
+use crate::runner;
 use anyhow::Result;
-use std::collections::HashMap;
 
-#[derive(Debug, Hash, PartialEq, Eq)]
-struct Point {
-    row: usize,
-    col: usize,
-}
-
-fn process(input: &str) -> u32 {
-    let mut numbers = HashMap::new();
-    let mut gears = vec![];
-
-    for (row, line) in input.lines().into_iter().enumerate() {
-        let mut number: Option<u32> = None;
-
-        for (col, char) in line.chars().enumerate() {
-            if let Some(digit) = char.to_digit(10) {
-                number = Some(number.unwrap_or_default() * 10 + digit);
-            } else {
-                if let Some(value) = number {
-                    let digits = (value.checked_ilog10().unwrap_or_default() + 1) as usize;
-
-                    for offset in 0..digits {
-                        numbers.insert(
-                            Point {
-                                row,
-                                col: col - offset - 1,
-                            },
-                            value,
-                        );
-                    }
-
-                    number = None;
-                }
-
-                if char == '*' {
-                    gears.push(Point { row, col });
-                }
-            }
-        }
-
-        if let Some(value) = number {
-            let digits = (value.checked_ilog10().unwrap_or_default() + 1) as usize;
-
-            for offset in 0..digits {
-                numbers.insert(
-                    Point {
-                        row,
-                        col: line.len() - offset - 1,
-                    },
-                    value,
-                );
-            }
-        }
-    }
-
-    gears
-        .iter()
-        .filter_map(|n| {
-            let mut a = None;
-            let mut b = None;
-
-            for rel_col in 0..3 {
-                for rel_row in 0..3 {
-                    let offset_col = rel_col + n.col;
-                    let offset_row = rel_row + n.row;
-
-                    if offset_col > 0 && offset_row > 0 {
-                        if let Some(value) = numbers.get(&Point {
-                            row: offset_row - 1,
-                            col: offset_col - 1,
-                        }) {
-                            match a {
-                                Some(a) => {
-                                    if a == *value {
-                                        continue;
-                                    } else {
-                                        match b {
-                                            Some(b) => {
-                                                if b == *value {
-                                                    continue;
-                                                } else {
-                                                    return None;
-                                                }
-                                            }
-                                            None => b = Some(*value),
-                                        }
-                                    }
-                                }
-                                None => a = Some(*value),
-                            }
-                        }
-                    }
-                }
-            }
+fn solve<T: Int>(schematic: &Schematic) -> Result<T> {
+    let ratios = schematic
+        .symbols_where(|c| c == '*', Degree::Exactly(2))
+        .map(|gear| schematic.product_around(gear))
+        .collect::<Result<Vec<T>>>()?;
 
-            if let Some(a) = a {
-                if let Some(b) = b {
-                    return Some(a * b);
-                }
-            }
+    num::sum(ratios, "sum of gear ratios")
+}
 
-            None
-        })
-        .sum()
+#[cfg(test)]
+fn process<T: Int>(input: &str) -> Result<T> {
+    solve(&Schematic::parse(input)?)
 }
 
 #[cfg(test)]
@@ -111,7 +23,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test() {
+    fn test() -> Result<()> {
         let input = "467..114..
 ...*......
 ..35..633.
@@ -123,11 +35,39 @@ mod tests {
 ...$.*....
 .664.598..";
 
-        assert_eq!(process(input), 467835);
+        assert_eq!(process::<u32>(input)?, 467835);
+
+        Ok(())
+    }
+
+    #[test]
+    fn equal_valued_neighbours() -> Result<()> {
+        assert_eq!(process::<u32>("12*12")?, 144);
+        assert_eq!(process::<u32>("5..\n*..\n5..")?, 25);
+        assert_eq!(process::<u32>("3.3\n.*.\n...")?, 9);
+        assert_eq!(
+            process::<u32>("12*12\n..12.")?,
+            0,
+            "three numbers is not a gear"
+        );
+        assert_eq!(
+            process::<u32>("123\n*..\n..4")?,
+            0,
+            "one number touching twice"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn non_ascii_noise() -> Result<()> {
+        assert_eq!(process::<u32>("ü·.12\n..*...\n34é...")?, 408);
+
+        Ok(())
     }
 }
 
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "....937..........309.............191..............745.................913......................................256................891.......
 ................*..................-..113.......3*..............219.........495%................40....97.......*.......670.60..../..........
 ......919..928..511......................................%......#...&...................................*.................*.................
@@ -268,9 +208,18 @@ pub fn main() -> Result<()> {
 548...............642...393*469.@.......$....*......................172.........@......860..........170......15.............-.....*.........
 ...@...........*.....*...........155..........998.........657.851-.....*...............................*.....&.........972.751.249..........
 ......898...561.186...207....270.....................................968...231..181..................324.........696........................";
-    let sum = process(&input);
+    let neighbourhood = Neighbourhood::from_param()?;
+    let schematic = mem::phase("parse", || Schematic::parse_with(input, &neighbourhood))?;
+
+    let style = Style::from_param()?;
+
+    if runner::explaining() {
+        runner::explain(&schematic.render(style.unwrap_or(Style::Markup))?);
+    }
 
-    println!("3.2: {}", sum);
+    let sum = mem::phase("solve", || {
+        num::widen(|| solve::<u32>(&schematic), || solve::<u128>(&schematic))
+    })?;
 
-    Ok(())
+    Ok(sum)
 }
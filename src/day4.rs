@@ -0,0 +1,582 @@
+use crate::num::{self, Int};
+use crate::runner;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scratchcard {
+    pub id: usize,
+    pub winning: Vec<u32>,
+    pub have: Vec<u32>,
+}
+
+impl Scratchcard {
+    /// How many of the numbers we have are winning numbers.
+    pub fn matches(&self) -> usize {
+        let mut winning = Bitset::default();
+
+        if !self.winning.iter().all(|&number| winning.insert(number)) {
+            return self
+                .have
+                .iter()
+                .filter(|number| self.winning.contains(number))
+                .count();
+        }
+
+        self.have
+            .iter()
+            .filter(|&&number| winning.contains(number))
+            .count()
+    }
+}
+
+/// The numbers below 256 as bits, which covers every real card without allocating.
+#[derive(Debug, Default)]
+struct Bitset([u64; 4]);
+
+impl Bitset {
+    /// Adds `number`, or returns false if it is too large to be held.
+    fn insert(&mut self, number: u32) -> bool {
+        match self.0.get_mut(number as usize / 64) {
+            Some(word) => {
+                *word |= 1 << (number % 64);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn contains(&self, number: u32) -> bool {
+        self.0
+            .get(number as usize / 64)
+            .is_some_and(|word| word & 1 << (number % 64) != 0)
+    }
+}
+
+fn numbers(list: &str, name: &str) -> Result<Vec<u32>> {
+    let mut numbers = vec![];
+
+    for token in list.split_ascii_whitespace() {
+        let number: u32 = token
+            .parse()
+            .with_context(|| format!("{:?} in the {} numbers is not a number", token, name))?;
+
+        if numbers.contains(&number) {
+            bail!("{} appears more than once in the {} numbers", number, name);
+        }
+
+        numbers.push(number);
+    }
+
+    Ok(numbers)
+}
+
+impl FromStr for Scratchcard {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Scratchcard> {
+        let (header, lists) = line
+            .split_once(':')
+            .ok_or(anyhow!("expected \"Card <id>: ...\""))?;
+        let id = header
+            .strip_prefix("Card")
+            .ok_or(anyhow!("expected the line to start with \"Card\""))?
+            .trim()
+            .parse()
+            .with_context(|| format!("{:?} has no card id", header))?;
+        let (winning, have) = lists
+            .split_once('|')
+            .ok_or(anyhow!("expected \"|\" between the two lists of numbers"))?;
+
+        Ok(Scratchcard {
+            id,
+            winning: numbers(winning, "winning")?,
+            have: numbers(have, "card's")?,
+        })
+    }
+}
+
+/// Parses one card per line, numbered from 1 without gaps.
+pub fn parse(input: &str) -> Result<Vec<Scratchcard>> {
+    let mut cards: Vec<Scratchcard> = vec![];
+
+    for (index, line) in input.lines().enumerate() {
+        let card: Scratchcard = line
+            .parse()
+            .with_context(|| format!("line {}: {:?}", index + 1, line))?;
+
+        if card.id != index + 1 {
+            bail!(
+                "line {}: expected card {}, found card {}",
+                index + 1,
+                index + 1,
+                card.id
+            );
+        }
+
+        cards.push(card);
+    }
+
+    Ok(cards)
+}
+
+/// How a pile of scratchcards is scored: either points per card, or copies of later cards won.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// One point for the first match, doubled for every match after it.
+    Doubling,
+    /// One point per match.
+    Linear,
+    /// 1, 2, 3, 5, 8, … points for one, two, three, four, five, … matches.
+    Fibonacci,
+    /// Each instance wins a copy of each of the next `matches` cards.
+    CopyNext,
+    /// Like [`Rule::CopyNext`], but never copies more than this many cards.
+    CopyWithCap(usize),
+}
+
+impl Rule {
+    /// Reads `doubling`, `linear`, `fibonacci`, `copy-next` or `copy-with-cap:N`.
+    pub fn parse(spec: &str) -> Result<Rule> {
+        match spec.trim() {
+            "doubling" => Ok(Rule::Doubling),
+            "linear" => Ok(Rule::Linear),
+            "fibonacci" => Ok(Rule::Fibonacci),
+            "copy-next" => Ok(Rule::CopyNext),
+            spec => match spec.strip_prefix("copy-with-cap:") {
+                Some(cap) => {
+                    Ok(Rule::CopyWithCap(cap.parse().with_context(|| {
+                        format!("{:?} is not a number of cards", cap)
+                    })?))
+                }
+                None => bail!(
+                    "Expected doubling, linear, fibonacci, copy-next or copy-with-cap:N, got {:?}",
+                    spec
+                ),
+            },
+        }
+    }
+
+    /// The rule for one part, from `--param scoring.1=...` or `--param scoring.2=...`, so each
+    /// part can be rescored on its own.
+    pub fn from_param(part: u32, default: Rule) -> Result<Rule> {
+        runner::param(&format!("scoring.{}", part))
+            .map(|spec| Rule::parse(&spec))
+            .unwrap_or(Ok(default))
+    }
+
+    /// How many of the following cards a card with `matches` matches copies.
+    pub fn copied(&self, matches: usize) -> usize {
+        match *self {
+            Rule::CopyNext => matches,
+            Rule::CopyWithCap(cap) => matches.min(cap),
+            Rule::Doubling | Rule::Linear | Rule::Fibonacci => 0,
+        }
+    }
+
+    fn points<T: Int>(&self, matches: usize) -> Result<T> {
+        let (mut points, mut next) = (T::ZERO, T::ONE);
+
+        for _ in 0..matches {
+            (points, next) = match self {
+                Rule::Doubling if points == T::ZERO => (T::ONE, T::ONE),
+                Rule::Doubling => (
+                    num::mul(points, num::cast(2u32, "base")?, "card score")?,
+                    next,
+                ),
+                Rule::Linear => (num::add(points, T::ONE, "card score")?, next),
+                Rule::Fibonacci if points == T::ZERO => (T::ONE, num::cast(2u32, "base")?),
+                Rule::Fibonacci => (next, num::add(points, next, "card score")?),
+                Rule::CopyNext | Rule::CopyWithCap(_) => return Ok(T::ZERO),
+            };
+        }
+
+        Ok(points)
+    }
+
+    /// The total points of every card, or the total number of card instances once the copies
+    /// have been handed out.
+    pub fn score<T: Int>(&self, cards: &[Scratchcard]) -> Result<T> {
+        match self {
+            Rule::Doubling | Rule::Linear | Rule::Fibonacci => num::sum(
+                cards
+                    .iter()
+                    .map(|card| self.points(card.matches()))
+                    .collect::<Result<Vec<T>>>()?,
+                "total score",
+            ),
+            Rule::CopyNext | Rule::CopyWithCap(_) => self.instances(cards),
+        }
+    }
+
+    /// Each card's instances hand one copy to each of the cards it copies, so rather than
+    /// visiting those cards we record where the run of copies stops and keep a running total.
+    fn instances<T: Int>(&self, cards: &[Scratchcard]) -> Result<T> {
+        let mut total_instances = T::ZERO;
+        let mut copies = T::ZERO;
+        let mut expiring = vec![T::ZERO; cards.len() + 1];
+
+        for (index, card) in cards.iter().enumerate() {
+            copies = num::sub(copies, expiring[index], "card copies")?;
+
+            let instances = num::add(T::ONE, copies, "card instances")?;
+            total_instances = num::add(total_instances, instances, "total card instances")?;
+
+            let copied = self.copied(card.matches()).min(cards.len() - index - 1);
+
+            if copied > 0 {
+                copies = num::add(copies, instances, "card copies")?;
+
+                let end = &mut expiring[index + 1 + copied];
+                *end = num::add(*end, instances, "card copies")?;
+            }
+        }
+
+        Ok(total_instances)
+    }
+}
+
+/// Copies one card handed to a later one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Source {
+    pub card: usize,
+    pub copies: u128,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CardReport {
+    pub id: usize,
+    pub matches: usize,
+    pub copies: u128,
+    pub from: Vec<Source>,
+    pub instances: u128,
+}
+
+/// How every card of part 2 came by its instances, card by card.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Cascade {
+    pub cards: Vec<CardReport>,
+    pub total_instances: u128,
+}
+
+impl Cascade {
+    /// Fails for the points rules, which copy no cards and so leave nothing to cascade.
+    pub fn new(cards: &[Scratchcard], rule: Rule) -> Result<Cascade> {
+        if let Rule::Doubling | Rule::Linear | Rule::Fibonacci = rule {
+            bail!("{:?} scores points and copies no cards", rule);
+        }
+
+        let mut reports: Vec<CardReport> = cards
+            .iter()
+            .map(|card| CardReport {
+                id: card.id,
+                matches: card.matches(),
+                copies: 0,
+                from: vec![],
+                instances: 1,
+            })
+            .collect();
+        let mut total_instances = 0;
+
+        for index in 0..reports.len() {
+            let (done, rest) = reports.split_at_mut(index + 1);
+            let card = &mut done[index];
+
+            card.copies = num::sum(card.from.iter().map(|s| s.copies), "card copies")?;
+            card.instances = num::add(1, card.copies, "card instances")?;
+            total_instances = num::add(total_instances, card.instances, "total card instances")?;
+
+            for later in rest.iter_mut().take(rule.copied(card.matches)) {
+                later.from.push(Source {
+                    card: card.id,
+                    copies: card.instances,
+                });
+            }
+        }
+
+        Ok(Cascade {
+            cards: reports,
+            total_instances,
+        })
+    }
+}
+
+impl Display for Cascade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows: Vec<[String; 5]> = self
+            .cards
+            .iter()
+            .map(|card| {
+                let from = card
+                    .from
+                    .iter()
+                    .map(|s| format!("{} ({})", s.card, s.copies))
+                    .collect::<Vec<_>>();
+
+                [
+                    card.id.to_string(),
+                    card.matches.to_string(),
+                    card.copies.to_string(),
+                    card.instances.to_string(),
+                    from.join(", "),
+                ]
+            })
+            .collect();
+        let header = ["card", "matches", "copies", "instances", "from"];
+        let widths: Vec<usize> = (0..4)
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].len())
+                    .chain([header[column].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        for row in [header.map(String::from)].iter().chain(rows.iter()) {
+            let line = format!(
+                "{:>w0$}  {:>w1$}  {:>w2$}  {:>w3$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                row[4],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+                w3 = widths[3],
+            );
+
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        writeln!(f, "total instances: {}", self.total_instances)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CardPoints {
+    pub id: usize,
+    pub matches: usize,
+    pub points: u128,
+}
+
+/// What every card scored under one of the points rules, card by card.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Tally {
+    pub cards: Vec<CardPoints>,
+    pub total_points: u128,
+}
+
+impl Tally {
+    /// Fails for the copying rules, which score no points.
+    pub fn new(cards: &[Scratchcard], rule: Rule) -> Result<Tally> {
+        if let Rule::CopyNext | Rule::CopyWithCap(_) = rule {
+            bail!("{:?} copies cards and scores no points", rule);
+        }
+
+        let cards = cards
+            .iter()
+            .map(|card| {
+                let matches = card.matches();
+
+                Ok(CardPoints {
+                    id: card.id,
+                    matches,
+                    points: rule.points(matches)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let total_points = num::sum(cards.iter().map(|card| card.points), "total score")?;
+
+        Ok(Tally {
+            cards,
+            total_points,
+        })
+    }
+}
+
+impl Display for Tally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows: Vec<[String; 3]> = self
+            .cards
+            .iter()
+            .map(|card| {
+                [
+                    card.id.to_string(),
+                    card.matches.to_string(),
+                    card.points.to_string(),
+                ]
+            })
+            .collect();
+        let header = ["card", "matches", "points"];
+        let widths: Vec<usize> = (0..3)
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].len())
+                    .chain([header[column].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        for row in [header.map(String::from)].iter().chain(rows.iter()) {
+            writeln!(
+                f,
+                "{:>w0$}  {:>w1$}  {:>w2$}",
+                row[0],
+                row[1],
+                row[2],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+            )?;
+        }
+
+        writeln!(f, "total points: {}", self.total_points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn parsing() -> Result<()> {
+        let cards = parse("Card 1: 41 48 83 | 83 86  6\nCard   2:  1 21 | 69  1")?;
+
+        assert_eq!(
+            cards,
+            vec![
+                Scratchcard {
+                    id: 1,
+                    winning: vec![41, 48, 83],
+                    have: vec![83, 86, 6],
+                },
+                Scratchcard {
+                    id: 2,
+                    winning: vec![1, 21],
+                    have: vec![69, 1],
+                }
+            ]
+        );
+        assert_eq!(cards[0].matches(), 1);
+        assert_eq!(cards[1].matches(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn large_numbers_still_match() -> Result<()> {
+        let cards = parse("Card 1: 255 256 1000 | 1000 255 7 256")?;
+
+        assert_eq!(cards[0].matches(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn validation() {
+        let error = |input: &str| format!("{:#}", parse(input).unwrap_err());
+
+        assert_eq!(
+            error("Card 1: 1 | 2\nCard 3: 1 | 2"),
+            "line 2: expected card 2, found card 3"
+        );
+        assert_eq!(
+            error("Card 1: 1 5 1 | 2"),
+            "line 1: \"Card 1: 1 5 1 | 2\": 1 appears more than once in the winning numbers"
+        );
+        assert_eq!(
+            error("Card 1: 1 | 2 x"),
+            "line 1: \"Card 1: 1 | 2 x\": \"x\" in the card's numbers is not a number: \
+             invalid digit found in string"
+        );
+        assert!(error("Card 1: 1 2").ends_with("expected \"|\" between the two lists of numbers"));
+        assert!(error("Game 1: 1 | 2").ends_with("expected the line to start with \"Card\""));
+    }
+
+    #[test]
+    fn cascade() -> Result<()> {
+        let cards = parse(EXAMPLE)?;
+        let cascade = Cascade::new(&cards, Rule::CopyNext)?;
+
+        assert_eq!(
+            cascade.to_string(),
+            "card  matches  copies  instances  from
+   1        4       0          1
+   2        2       1          2  1 (1)
+   3        2       3          4  1 (1), 2 (2)
+   4        1       7          8  1 (1), 2 (2), 3 (4)
+   5        0      13         14  1 (1), 3 (4), 4 (8)
+   6        0       0          1
+total instances: 30
+"
+        );
+        assert_eq!(
+            serde_json::to_value(&cascade.cards[1])?,
+            serde_json::json!({
+                "id": 2,
+                "matches": 2,
+                "copies": 1,
+                "from": [{ "card": 1, "copies": 1 }],
+                "instances": 2
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn tally() -> Result<()> {
+        let cards = parse(EXAMPLE)?;
+
+        assert_eq!(
+            Tally::new(&cards, Rule::Doubling)?.to_string(),
+            "card  matches  points
+   1        4       8
+   2        2       2
+   3        2       2
+   4        1       1
+   5        0       0
+   6        0       0
+total points: 13
+"
+        );
+        assert_eq!(Tally::new(&cards, Rule::Fibonacci)?.total_points, 10);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rules() -> Result<()> {
+        let cards = parse(EXAMPLE)?;
+        let score = |spec: &str| Rule::parse(spec)?.score::<u32>(&cards);
+
+        assert_eq!(score("doubling")?, 13);
+        assert_eq!(score("linear")?, 9);
+        assert_eq!(score("fibonacci")?, 10);
+        assert_eq!(score("copy-next")?, 30);
+        assert_eq!(score("copy-with-cap:1")?, 16);
+        assert_eq!(score("copy-with-cap:9")?, 30);
+        assert_eq!(
+            Cascade::new(&cards, Rule::CopyWithCap(1))?.total_instances,
+            16
+        );
+        assert!(Cascade::new(&cards, Rule::Linear).is_err());
+        assert!(Tally::new(&cards, Rule::CopyNext).is_err());
+        assert!(Rule::parse("copy-with-cap:x").is_err());
+        assert!(Rule::parse("tripling").is_err());
+
+        Ok(())
+    }
+}
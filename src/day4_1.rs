@@ -1,52 +1,11 @@
+use crate::day4::{parse, Rule};
+use crate::mem;
+use crate::num;
 use anyhow::Result;
 
-fn process(input: &str) -> Result<i32> {
-    let mut total = 0;
-
-    for line in input.lines() {
-        let mut input = line.split(": ");
-        // let header = input.nth(0).unwrap();
-        // let id = header.split(' ').nth(0).unwrap();
-        // let id: usize = id.parse().unwrap();
-
-        let mut data = input.nth(1).unwrap().split('|');
-        let winning: Vec<usize> = data
-            .nth(0)
-            .unwrap()
-            .split(' ')
-            .filter_map(|x| {
-                if x.len() > 0 {
-                    Some(x.parse().unwrap())
-                } else {
-                    None
-                }
-            })
-            .collect();
-        let numbers: Vec<usize> = data
-            .nth(0)
-            .unwrap()
-            .split(' ')
-            .filter_map(|x| {
-                if x.len() > 0 {
-                    Some(x.parse().unwrap())
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        let mut score = 0;
-
-        for number in numbers {
-            if winning.contains(&number) {
-                score = if score == 0 { 1 } else { score * 2 };
-            }
-        }
-
-        total += score;
-    }
-
-    Ok(total)
+#[cfg(test)]
+fn process<T: num::Int>(input: &str) -> Result<T> {
+    Rule::Doubling.score(&parse(input)?)
 }
 
 #[cfg(test)]
@@ -62,13 +21,13 @@ Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
-        assert_eq!(process(input)?, 13);
+        assert_eq!(process::<u32>(input)?, 13);
 
         Ok(())
     }
 }
 
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "Card   1: 20 72 30 38 18 65  6 55 70 27 | 12 28 47 50 60 17 14 25 41 95 66 88 61 52 76  5 23 77 31 32 99 89 53 54 96
 Card   2: 15 98 12  3 20 60 58 54 34 18 | 98 23 12 19 61 38 11 43 58 97 63 10 49 67 44 52 88  4 22  1 42 65 20 13 25
 Card   3: 47 97  2 80 89 56 66 85 62 46 | 78 97 47 14 15 85 42 66 24 28 54 46 89 62 80 56 22  5 57 52 69  2  3 95 19
@@ -257,7 +216,11 @@ Card 185: 95 13 15 61 49 19 60 21 33 87 | 30 36 52 88 23 65 95 25 53 51 77 32 66
 Card 186: 41 48 94 68 60 15 22 55 84  2 | 57 53  9 50  4 16 11 62 61  6 46 52  8 35 23 39 51 72 43 22 81 56 77 45 19
 Card 187: 57 81 33  3 42 78 83 30  2  9 | 14 85 99  1 55 54 66 56 26 21 12 86 20 39 37 41 94 15 24 76 91 73 44 36  8";
 
-    println!("4.1: {}", process(&input)?);
+    let rule = Rule::from_param(1, Rule::Doubling)?;
+    let cards = mem::phase("parse", || parse(input))?;
+    let result = mem::phase("solve", || {
+        num::widen(|| rule.score::<u32>(&cards), || rule.score::<u128>(&cards))
+    })?;
 
-    Ok(())
+    Ok(result)
 }
@@ -1,77 +1,147 @@
-use anyhow::{anyhow, Result};
-use std::collections::HashSet;
+use crate::day4::{parse, Cascade, Rule, Tally};
+use crate::mem;
+use crate::num;
+use crate::runner;
+use anyhow::Result;
 
-struct Adder {
-    instances: usize,
-    lifetime: usize,
+#[cfg(test)]
+fn process<T: num::Int>(input: &str) -> Result<T> {
+    Rule::CopyNext.score(&parse(input)?)
 }
 
-fn process(input: &str) -> Result<usize> {
-    let mut total_instances = 0;
-    let mut stack: Vec<Adder> = vec![];
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day4::Scratchcard;
+    use crate::testing::Xorshift;
+    use std::collections::HashSet;
 
-    for line in input.lines() {
-        let mut sections = line
-            .split(": ")
-            .nth(1)
-            .ok_or(anyhow!("Expected line be to be splittable by \": \""))?
-            .split(" | ");
+    #[test]
+    fn test() -> Result<()> {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
-        let winning: HashSet<usize> = sections
-            .next()
-            .ok_or(anyhow!("Expected line be to splittable by \" | \""))?
-            .split_ascii_whitespace()
-            .filter_map(|c| if let Ok(n) = c.parse() { Some(n) } else { None })
-            .collect();
-        let numbers: HashSet<usize> = sections
-            .next()
-            .ok_or(anyhow!("Expected line be to splittable by \" | \""))?
-            .split_ascii_whitespace()
-            .filter_map(|c| if let Ok(n) = c.parse() { Some(n) } else { None })
-            .collect();
+        assert_eq!(process::<u32>(input)?, 30);
+
+        Ok(())
+    }
+
+    struct Adder {
+        instances: u128,
+        lifetime: usize,
+    }
 
-        let score = numbers.intersection(&winning).count();
-        let copies = stack.iter().fold(0, |a, s| a + s.instances);
-        let instances = 1 + copies;
+    /// The cascade this replaced, which walks every live run of copies on every card.
+    fn stacked(cards: &[(HashSet<u32>, HashSet<u32>)]) -> u128 {
+        let mut total_instances = 0;
+        let mut stack: Vec<Adder> = vec![];
 
-        total_instances += instances;
+        for (winning, have) in cards {
+            let score = have.intersection(winning).count();
+            let instances = 1 + stack.iter().map(|s| s.instances).sum::<u128>();
 
-        stack.retain_mut(|i| {
-            i.lifetime -= 1;
-            i.lifetime > 0
-        });
+            total_instances += instances;
 
-        if score > 0 {
-            stack.push(Adder {
-                instances,
-                lifetime: score,
+            stack.retain_mut(|i| {
+                i.lifetime -= 1;
+                i.lifetime > 0
             });
+
+            if score > 0 {
+                stack.push(Adder {
+                    instances,
+                    lifetime: score,
+                });
+            }
         }
+
+        total_instances
     }
 
-    Ok(total_instances)
-}
+    /// Mostly losing cards with the odd big win, so the number of instances stays bounded.
+    fn generated_cards(count: usize) -> Vec<Scratchcard> {
+        let mut random = Xorshift::default();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        (1..=count)
+            .map(|id| {
+                let mut numbers: Vec<u32> = (1..100).collect();
+
+                for i in 0..35 {
+                    numbers.swap(i, i + random.below(99 - i));
+                }
+
+                let matches = if random.below(10) == 0 {
+                    random.below(11)
+                } else {
+                    0
+                };
+
+                Scratchcard {
+                    id,
+                    winning: numbers[..10].to_vec(),
+                    have: numbers[10 - matches..35 - matches].to_vec(),
+                }
+            })
+            .collect()
+    }
 
     #[test]
-    fn test() -> Result<()> {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+    fn matches_stacked_cascade() -> Result<()> {
+        let cards = generated_cards(2000);
+        let sets: Vec<_> = cards
+            .iter()
+            .map(|c| {
+                (
+                    c.winning.iter().copied().collect(),
+                    c.have.iter().copied().collect(),
+                )
+            })
+            .collect();
 
-        assert_eq!(process(input)?, 30);
+        assert_eq!(Rule::CopyNext.score::<u128>(&cards)?, stacked(&sets));
+
+        Ok(())
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_against_stacked_cascade() -> Result<()> {
+        let cards = generated_cards(1_000_000);
+        let sets: Vec<_> = cards
+            .iter()
+            .map(|c| {
+                (
+                    c.winning.iter().copied().collect(),
+                    c.have.iter().copied().collect(),
+                )
+            })
+            .collect();
+
+        let started = std::time::Instant::now();
+        let stacked_total = stacked(&sets);
+        let stacked_time = started.elapsed();
+
+        let started = std::time::Instant::now();
+        let total = Rule::CopyNext.score::<u128>(&cards)?;
+        let linear_time = started.elapsed();
+
+        assert_eq!(total, stacked_total);
+        println!(
+            "{} cards: stacked {:?}, difference array {:?}",
+            cards.len(),
+            stacked_time,
+            linear_time
+        );
 
         Ok(())
     }
 }
 
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "Card   1: 20 72 30 38 18 65  6 55 70 27 | 12 28 47 50 60 17 14 25 41 95 66 88 61 52 76  5 23 77 31 32 99 89 53 54 96
 Card   2: 15 98 12  3 20 60 58 54 34 18 | 98 23 12 19 61 38 11 43 58 97 63 10 49 67 44 52 88  4 22  1 42 65 20 13 25
 Card   3: 47 97  2 80 89 56 66 85 62 46 | 78 97 47 14 15 85 42 66 24 28 54 46 89 62 80 56 22  5 57 52 69  2  3 95 19
@@ -260,7 +330,21 @@ Card 185: 95 13 15 61 49 19 60 21 33 87 | 30 36 52 88 23 65 95 25 53 51 77 32 66
 Card 186: 41 48 94 68 60 15 22 55 84  2 | 57 53  9 50  4 16 11 62 61  6 46 52  8 35 23 39 51 72 43 22 81 56 77 45 19
 Card 187: 57 81 33  3 42 78 83 30  2  9 | 14 85 99  1 55 54 66 56 26 21 12 86 20 39 37 41 94 15 24 76 91 73 44 36  8";
 
-    println!("4.2: {}", process(&input)?);
+    let rule = Rule::from_param(2, Rule::CopyNext)?;
+    let cards = mem::phase("parse", || parse(input))?;
+
+    if runner::explaining() {
+        match rule {
+            Rule::CopyNext | Rule::CopyWithCap(_) => runner::explain(&Cascade::new(&cards, rule)?),
+            Rule::Doubling | Rule::Linear | Rule::Fibonacci => {
+                runner::explain(&Tally::new(&cards, rule)?)
+            }
+        }
+    }
+
+    let result = mem::phase("solve", || {
+        num::widen(|| rule.score::<u32>(&cards), || rule.score::<u128>(&cards))
+    })?;
 
-    Ok(())
+    Ok(result)
 }
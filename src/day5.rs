@@ -0,0 +1,264 @@
+use crate::graph::{self, AdjacencyList, Graph};
+use crate::interval::RangeMap;
+use crate::runner;
+use anyhow::{anyhow, bail, Result};
+use nom::{
+    bytes::complete::{tag, take_until},
+    character::complete::{line_ending, multispace1, space1, u64},
+    multi::separated_list1,
+    sequence::{preceded, terminated, tuple},
+    IResult,
+};
+use std::collections::HashMap;
+
+#[derive(Debug)]
+struct Thruple {
+    dest_range_start: u64,
+    src_range_start: u64,
+    range: u64,
+}
+
+fn parse_thruple(input: &str) -> IResult<&str, Thruple> {
+    let (rest, values) = tuple((u64, preceded(space1, u64), preceded(space1, u64)))(input)?;
+
+    Ok((
+        rest,
+        Thruple {
+            dest_range_start: values.0,
+            src_range_start: values.1,
+            range: values.2,
+        },
+    ))
+}
+
+fn range_map(thruples: &[Thruple]) -> Result<RangeMap> {
+    let mut map = RangeMap::new();
+
+    for thruple in thruples.iter() {
+        // Checked whatever `--checked` says, since the map itself relies on these not wrapping.
+        for (start, what) in [
+            (thruple.src_range_start, "source"),
+            (thruple.dest_range_start, "destination"),
+        ] {
+            start.checked_add(thruple.range).ok_or(anyhow!(
+                "The {} range {} + {} is past the end of u64",
+                what,
+                start,
+                thruple.range
+            ))?;
+        }
+
+        map.insert(
+            thruple.src_range_start,
+            thruple.dest_range_start,
+            thruple.range,
+        );
+    }
+
+    Ok(map)
+}
+
+fn parse_block(input: &str) -> IResult<&str, (&str, Vec<Thruple>)> {
+    let (rest, header) = terminated(take_until(" map:"), tag(" map:"))(input)?;
+    let (rest, thruples) =
+        preceded(line_ending, separated_list1(line_ending, parse_thruple))(rest)?;
+
+    Ok((rest, (header, thruples)))
+}
+
+/// One `source-to-destination map:` and the values it moves between the two categories.
+#[derive(Debug)]
+pub struct Block {
+    pub source: String,
+    pub destination: String,
+    pub map: RangeMap,
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    pub blocks: Vec<Block>,
+}
+
+impl Almanac {
+    /// Reads the maps that follow the seeds, whatever categories they name.
+    pub fn parse(input: &str) -> Result<Almanac> {
+        let (_, blocks) =
+            separated_list1(multispace1, parse_block)(input).map_err(|e| e.to_owned())?;
+        let blocks = blocks
+            .into_iter()
+            .map(|(header, thruples)| {
+                let (source, destination) = header
+                    .split_once("-to-")
+                    .filter(|(s, d)| !s.is_empty() && !d.is_empty())
+                    .ok_or(anyhow!(
+                        "Expected a \"source-to-destination map:\" header, got {:?}",
+                        header
+                    ))?;
+
+                Ok(Block {
+                    source: source.to_string(),
+                    destination: destination.to_string(),
+                    map: range_map(&thruples)?,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Almanac { blocks })
+    }
+
+    /// The blocks that take a seed to `target`, in the order they apply. The maps may form any
+    /// graph, as long as there is no loop on the way and exactly one way through.
+    pub fn route(&self, target: &str) -> Result<Vec<&Block>> {
+        let mut categories = AdjacencyList::new();
+
+        for block in self.blocks.iter() {
+            categories.add_edge(block.source.as_str(), block.destination.as_str(), 1);
+        }
+
+        if let Some(cycle) = graph::find_cycle(&categories, "seed") {
+            bail!("The maps go round in a loop: {}", cycle.join(" -> "));
+        }
+
+        let path = graph::bfs_path(&categories, "seed", |category| *category == target)
+            .ok_or(anyhow!("No chain of maps leads from seed to {}", target))?;
+
+        let routes = count_routes(&categories, "seed", target, &mut HashMap::new());
+
+        if routes > 1 {
+            bail!("{} chains of maps lead from seed to {}", routes, target);
+        }
+
+        Ok(path
+            .windows(2)
+            .map(|pair| {
+                self.blocks
+                    .iter()
+                    .find(|b| b.source == pair[0] && b.destination == pair[1])
+                    .expect("a block for every edge")
+            })
+            .collect())
+    }
+}
+
+/// How many paths lead from `from` to `target` in a graph without cycles.
+fn count_routes<'a>(
+    categories: &AdjacencyList<&'a str>,
+    from: &'a str,
+    target: &str,
+    counted: &mut HashMap<&'a str, usize>,
+) -> usize {
+    if from == target {
+        return 1;
+    }
+
+    if let Some(&count) = counted.get(from) {
+        return count;
+    }
+
+    let count = categories
+        .neighbours(&from)
+        .into_iter()
+        .map(|(next, _)| count_routes(categories, next, target, counted))
+        .fold(0, usize::saturating_add);
+
+    counted.insert(from, count);
+
+    count
+}
+
+/// Every block on the route in turn as a single map.
+pub fn compose(route: &[&Block]) -> RangeMap {
+    route
+        .iter()
+        .fold(RangeMap::new(), |composed, block| composed.then(&block.map))
+}
+
+/// The category to map seeds to, from `--param target=...`.
+pub fn target_from_param() -> String {
+    runner::param("target").unwrap_or("location".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn almanac(headers: &[&str]) -> Result<Almanac> {
+        let input = headers
+            .iter()
+            .map(|header| format!("{} map:\n1 0 1", header))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        Almanac::parse(&input)
+    }
+
+    fn route(almanac: &Almanac, target: &str) -> Result<Vec<String>> {
+        Ok(almanac
+            .route(target)?
+            .iter()
+            .map(|block| format!("{}-to-{}", block.source, block.destination))
+            .collect())
+    }
+
+    #[test]
+    fn routes_through_any_categories() -> Result<()> {
+        let almanac = almanac(&[
+            "water-to-light",
+            "seed-to-soil",
+            "soil-to-water",
+            "soil-to-dust",
+            "light-to-location",
+        ])?;
+
+        assert_eq!(
+            route(&almanac, "location")?,
+            vec![
+                "seed-to-soil",
+                "soil-to-water",
+                "water-to-light",
+                "light-to-location"
+            ]
+        );
+        assert_eq!(
+            route(&almanac, "dust")?,
+            vec!["seed-to-soil", "soil-to-dust"]
+        );
+        assert!(route(&almanac, "seed")?.is_empty());
+        assert_eq!(
+            route(&almanac, "humidity").unwrap_err().to_string(),
+            "No chain of maps leads from seed to humidity"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_loops_and_ambiguity() -> Result<()> {
+        let looping = almanac(&["seed-to-soil", "soil-to-water", "water-to-soil"])?;
+        let forked = almanac(&[
+            "seed-to-soil",
+            "seed-to-water",
+            "soil-to-light",
+            "water-to-light",
+        ])?;
+
+        assert_eq!(
+            route(&looping, "water").unwrap_err().to_string(),
+            "The maps go round in a loop: soil -> water"
+        );
+        assert_eq!(
+            route(&forked, "light").unwrap_err().to_string(),
+            "2 chains of maps lead from seed to light"
+        );
+        assert_eq!(route(&forked, "soil")?, vec!["seed-to-soil"]);
+        assert!(almanac(&["seed-soil"]).is_err());
+        assert_eq!(
+            Almanac::parse("seed-to-soil map:\n0 18446744073709551615 2")
+                .unwrap_err()
+                .to_string(),
+            "The source range 18446744073709551615 + 2 is past the end of u64"
+        );
+
+        Ok(())
+    }
+}
@@ -1,10 +1,13 @@
+use crate::day5::{self, compose, Almanac};
+use crate::interval::RangeMap;
+use crate::mem;
+use crate::runner;
 use anyhow::{anyhow, Result};
 use nom::{
-    branch::alt,
     bytes::complete::tag,
-    character::complete::{alpha1, line_ending, multispace1, space1, u64},
-    multi::{many1, separated_list1},
-    sequence::{preceded, terminated, tuple},
+    character::complete::{multispace1, space1, u64},
+    multi::separated_list1,
+    sequence::{preceded, terminated},
     IResult,
 };
 
@@ -17,99 +20,25 @@ fn parse_seeds(input: &str) -> IResult<&str, Vec<u64>> {
     Ok((rest, numbers))
 }
 
-#[allow(unused)]
-#[derive(Debug)]
-struct Thruple {
-    dest_range_start: u64,
-    src_range_start: u64,
-    range: u64,
-}
-
-#[derive(Debug, Eq, Hash, PartialEq)]
-enum Block {
-    SeedToSoil,
-    SoilToFertilizer,
-    FertilizerToWater,
-    WaterToLight,
-    LightToTemperature,
-    TemperatureToHumidity,
-    HumidityToLocation,
-}
-
-impl Block {
-    fn from_str(input: &str) -> Result<Block> {
-        match input {
-            "seed-to-soil" => Ok(Block::SeedToSoil),
-            "soil-to-fertilizer" => Ok(Block::SoilToFertilizer),
-            "fertilizer-to-water" => Ok(Block::FertilizerToWater),
-            "water-to-light" => Ok(Block::WaterToLight),
-            "light-to-temperature" => Ok(Block::LightToTemperature),
-            "temperature-to-humidity" => Ok(Block::TemperatureToHumidity),
-            "humidity-to-location" => Ok(Block::HumidityToLocation),
-            _ => Err(anyhow!("eh")),
-        }
-    }
-}
-
-fn parse_thruple(input: &str) -> IResult<&str, Thruple> {
-    let (rest, values) = tuple((u64, preceded(space1, u64), preceded(space1, u64)))(input)?;
-
-    Ok((
-        &rest,
-        Thruple {
-            dest_range_start: values.0,
-            src_range_start: values.1,
-            range: values.2,
-        },
-    ))
-}
-
-fn parse_block(input: &str) -> IResult<&str, (Block, Vec<Thruple>)> {
-    let (rest, header) = terminated(
-        many1(alt((tag("-"), alpha1))),
-        terminated(tag(" map:"), line_ending),
-    )(input)?;
-
-    let block = Block::from_str(&header.join("")).unwrap();
-
-    let (rest, thruples) = separated_list1(line_ending, parse_thruple)(rest)?;
-
-    Ok((rest, (block, thruples)))
-}
-
-fn parse_blocks(input: &str) -> IResult<&str, Vec<(Block, Vec<Thruple>)>> {
-    let (rest, list) = separated_list1(multispace1, parse_block)(input)?;
-
-    Ok((rest, list.into_iter().collect()))
-}
-
-fn parse(input: &str) -> Result<(Vec<u64>, Vec<(Block, Vec<Thruple>)>)> {
+fn parse(input: &str) -> Result<(Vec<u64>, Almanac)> {
     let (rest, seeds) = parse_seeds(input).map_err(|e| e.to_owned())?;
-    let (_, blocks) = parse_blocks(rest).map_err(|e| e.to_owned())?;
 
-    Ok((seeds, blocks))
+    Ok((seeds, Almanac::parse(rest)?))
 }
 
-fn process(input: &str) -> Result<u64> {
-    let (seeds, blocks) = parse(input)?;
-
-    Ok(seeds
+fn solve(seeds: &[u64], almanac: &RangeMap) -> Result<u64> {
+    seeds
         .iter()
-        .map(|seed| {
-            blocks.iter().fold(*seed, |seed, (_, thruples)| {
-                for thruple in thruples.iter() {
-                    if seed >= thruple.src_range_start
-                        && seed < thruple.src_range_start + thruple.range
-                    {
-                        return seed + thruple.dest_range_start - thruple.src_range_start;
-                    }
-                }
-
-                seed
-            })
-        })
+        .map(|seed| almanac.apply(*seed))
         .min()
-        .ok_or(anyhow!("No blocks returned"))?)
+        .ok_or(anyhow!("No blocks returned"))
+}
+
+#[cfg(test)]
+fn process(input: &str, target: &str) -> Result<u64> {
+    let (seeds, almanac) = parse(input)?;
+
+    solve(&seeds, &compose(&almanac.route(target)?))
 }
 
 #[cfg(test)]
@@ -123,7 +52,7 @@ mod tests {
 seed-to-soil map:
 50 98 2";
 
-        assert_eq!(process(input)?, 50);
+        assert_eq!(process(input, "soil")?, 50);
 
         Ok(())
     }
@@ -165,7 +94,17 @@ humidity-to-location map:
 56 93 4
 ";
 
-        assert_eq!(process(input)?, 35);
+        assert_eq!(process(input, "location")?, 35);
+
+        let (_, almanac) = parse(input)?;
+        let route = almanac.route("location")?;
+        let composed = compose(&route);
+
+        for seed in 0..120 {
+            let stage_by_stage = route.iter().fold(seed, |seed, block| block.map.apply(seed));
+
+            assert_eq!(composed.apply(seed), stage_by_stage);
+        }
 
         Ok(())
     }
@@ -182,7 +121,7 @@ humidity-to-location map:
     }
 }
 
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "seeds: 919339981 562444630 3366006921 67827214 1496677366 101156779 4140591657 5858311 2566406753 71724353 2721360939 35899538 383860877 424668759 3649554897 442182562 2846055542 49953829 2988140126 256306471
 
 seed-to-soil map:
@@ -390,7 +329,16 @@ humidity-to-location map:
 1647757140 2247246174 9396637
 2434490733 4247201303 1535992";
 
-    println!("5.1: {}", process(input)?);
+    let target = day5::target_from_param();
+    let (seeds, almanac) = mem::phase("parse", || parse(input))?;
+    let route = almanac.route(&target)?;
+    let composed = mem::phase("compose", || compose(&route));
+
+    if runner::explaining() {
+        runner::explain(&composed);
+    }
+
+    let result = mem::phase("solve", || solve(&seeds, &composed))?;
 
-    Ok(())
+    Ok(result.to_string())
 }
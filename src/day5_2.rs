@@ -1,22 +1,28 @@
-use anyhow::{anyhow, Result};
+use crate::day5::{self, compose, Almanac, Block};
+use crate::interval::{IntervalSet, RangeMap};
+use crate::mem;
+use crate::runner;
+use anyhow::{anyhow, bail, Context, Result};
 use nom::{
-    branch::alt,
     bytes::complete::tag,
-    character::complete::{alpha1, digit1, line_ending, multispace1, space1},
-    multi::{many1, separated_list1},
-    sequence::{preceded, separated_pair, terminated, tuple},
+    character::complete::{multispace1, space1, u64},
+    error::{Error, ErrorKind},
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, terminated},
     IResult,
 };
-use rayon::prelude::*;
+use serde::Serialize;
+use std::fmt::Display;
 use std::ops::Range;
 
 fn parse_seed_range(input: &str) -> IResult<&str, Range<u64>> {
-    let (rest, (start, range)) = separated_pair(digit1, space1, digit1)(input)?;
+    let (rest, (start, range)) = separated_pair(u64, space1, u64)(input)?;
 
-    let start = start.parse().unwrap();
-    let range: u64 = range.parse().unwrap();
+    let end = start
+        .checked_add(range)
+        .ok_or(nom::Err::Failure(Error::new(input, ErrorKind::TooLarge)))?;
 
-    Ok((rest, start..start + range))
+    Ok((rest, start..end))
 }
 
 fn parse_seeds(input: &str) -> IResult<&str, Vec<Range<u64>>> {
@@ -28,102 +34,101 @@ fn parse_seeds(input: &str) -> IResult<&str, Vec<Range<u64>>> {
     Ok((rest, ranges))
 }
 
-#[allow(unused)]
-#[derive(Debug)]
-struct Thruple {
-    dest_range_start: u64,
-    src_range_start: u64,
-    range: u64,
-}
+fn parse(input: &str) -> Result<(Vec<Range<u64>>, Almanac)> {
+    let (rest, seeds) = parse_seeds(input).map_err(|e| e.to_owned())?;
 
-#[derive(Debug, Eq, Hash, PartialEq)]
-enum Block {
-    SeedToSoil,
-    SoilToFertilizer,
-    FertilizerToWater,
-    WaterToLight,
-    LightToTemperature,
-    TemperatureToHumidity,
-    HumidityToLocation,
+    Ok((seeds, Almanac::parse(rest)?))
 }
 
-impl Block {
-    fn from_str(input: &str) -> Result<Block> {
-        match input {
-            "seed-to-soil" => Ok(Block::SeedToSoil),
-            "soil-to-fertilizer" => Ok(Block::SoilToFertilizer),
-            "fertilizer-to-water" => Ok(Block::FertilizerToWater),
-            "water-to-light" => Ok(Block::WaterToLight),
-            "light-to-temperature" => Ok(Block::LightToTemperature),
-            "temperature-to-humidity" => Ok(Block::TemperatureToHumidity),
-            "humidity-to-location" => Ok(Block::HumidityToLocation),
-            _ => Err(anyhow!("eh")),
-        }
-    }
-}
+fn solve(seeds: &[Range<u64>], almanac: &RangeMap) -> Result<u64> {
+    let seeds: IntervalSet = seeds.iter().cloned().collect();
 
-fn parse_thruple(input: &str) -> IResult<&str, Thruple> {
-    let (rest, values) =
-        tuple((digit1, preceded(space1, digit1), preceded(space1, digit1)))(input)?;
-
-    Ok((
-        &rest,
-        Thruple {
-            dest_range_start: values.0.parse().unwrap(),
-            src_range_start: values.1.parse().unwrap(),
-            range: values.2.parse().unwrap(),
-        },
-    ))
+    almanac
+        .apply_set(&seeds)
+        .min()
+        .ok_or(anyhow!("No seeds to map"))
 }
 
-fn parse_block(input: &str) -> IResult<&str, (Block, Vec<Thruple>)> {
-    let (rest, header) = terminated(
-        many1(alt((tag("-"), alpha1))),
-        terminated(tag(" map:"), line_ending),
-    )(input)?;
+/// Where a range of values in the target category comes from, worked back one block at a time.
+#[derive(Debug, Serialize)]
+struct Origins {
+    locations: IntervalSet,
+    /// What each category had to hold, from the one before the target back to `seed`.
+    stages: Vec<(String, IntervalSet)>,
+    /// The values among the seed ranges that end up in `locations`.
+    seeds: IntervalSet,
+}
 
-    let block = Block::from_str(&header.join("")).unwrap();
+impl Origins {
+    fn new(locations: IntervalSet, seeds: &[Range<u64>], route: &[&Block]) -> Origins {
+        let mut stages = vec![];
+        let mut wanted = locations.clone();
 
-    let (rest, thruples) = separated_list1(line_ending, parse_thruple)(rest)?;
+        for block in route.iter().rev() {
+            wanted = block.map.preimage(&wanted);
+            stages.push((block.source.clone(), wanted.clone()));
+        }
 
-    Ok((rest, (block, thruples)))
+        Origins {
+            locations,
+            stages,
+            seeds: wanted.intersection(&seeds.iter().cloned().collect()),
+        }
+    }
 }
 
-fn parse_blocks(input: &str) -> IResult<&str, Vec<(Block, Vec<Thruple>)>> {
-    let (rest, list) = separated_list1(multispace1, parse_block)(input)?;
+impl Display for Origins {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "wanted: {}", self.locations)?;
 
-    Ok((rest, list.into_iter().collect()))
+        for (category, wanted) in self.stages.iter() {
+            writeln!(f, "{}: {}", category, wanted)?;
+        }
+
+        writeln!(f, "among the seeds: {}", self.seeds)
+    }
 }
 
-fn parse(input: &str) -> Result<(Vec<Range<u64>>, Vec<(Block, Vec<Thruple>)>)> {
-    let (rest, seeds) = parse_seeds(input).map_err(|e| e.to_owned())?;
-    let (_, blocks) = parse_blocks(rest).map_err(|e| e.to_owned())?;
+/// Reads `START..END` or a single location.
+fn parse_locations(spec: &str) -> Result<IntervalSet> {
+    let number = |n: &str| {
+        n.trim().parse::<u64>().with_context(|| {
+            format!(
+                "Expected locations=START..END or a location, got {:?}",
+                spec
+            )
+        })
+    };
+    let range = match spec.split_once("..") {
+        Some((start, end)) => number(start)?..number(end)?,
+        None => {
+            let location = number(spec)?;
+            let end = location
+                .checked_add(1)
+                .ok_or(anyhow!("Location {} is past the last one", location))?;
+
+            location..end
+        }
+    };
+
+    if range.start > range.end {
+        bail!("Locations {}..{} run backwards", range.start, range.end);
+    }
 
-    Ok((seeds, blocks))
+    Ok(IntervalSet::from(range))
 }
 
-fn process(input: &str) -> Result<u64> {
-    let (seeds, blocks) = parse(input)?;
-
-    Ok(seeds
-        .par_iter()
-        .flat_map_iter(|range| {
-            range.clone().map(|seed| {
-                blocks.iter().fold(seed, |seed, (_, thruples)| {
-                    for thruple in thruples.iter() {
-                        if seed >= thruple.src_range_start
-                            && seed < thruple.src_range_start + thruple.range
-                        {
-                            return seed + thruple.dest_range_start - thruple.src_range_start;
-                        }
-                    }
-
-                    seed
-                })
-            })
-        })
-        .min()
-        .ok_or(anyhow!("No blocks returned"))?)
+fn locations_from_param() -> Result<Option<IntervalSet>> {
+    runner::param("locations")
+        .map(|spec| parse_locations(&spec))
+        .transpose()
+}
+
+#[cfg(test)]
+fn process(input: &str, target: &str) -> Result<u64> {
+    let (seeds, almanac) = parse(input)?;
+
+    solve(&seeds, &compose(&almanac.route(target)?))
 }
 
 #[cfg(test)]
@@ -137,7 +142,7 @@ mod tests {
 seed-to-soil map:
 50 98 2";
 
-        assert_eq!(process(input)?, 50);
+        assert_eq!(process(input, "soil")?, 50);
 
         Ok(())
     }
@@ -179,7 +184,50 @@ humidity-to-location map:
 56 93 4
 ";
 
-        assert_eq!(process(input)?, 46);
+        assert_eq!(process(input, "location")?, 46);
+
+        let (seeds, almanac) = parse(input)?;
+        let route = almanac.route("location")?;
+        let seed_by_seed = seeds
+            .iter()
+            .flat_map(|range| range.clone())
+            .map(|seed| route.iter().fold(seed, |seed, block| block.map.apply(seed)))
+            .min();
+
+        assert_eq!(seed_by_seed, Some(46));
+
+        let seeds: IntervalSet = seeds.into_iter().collect();
+        let stage_by_stage = route
+            .iter()
+            .fold(seeds.clone(), |set, block| block.map.apply_set(&set));
+
+        assert_eq!(compose(&route).apply_set(&seeds), stage_by_stage);
+
+        let origins = Origins::new(IntervalSet::from(46..47), &[79..93, 55..68], &route);
+        let (_, wanted) = origins.stages.last().unwrap();
+
+        assert_eq!(origins.seeds, IntervalSet::from(82..83));
+        assert_eq!(*wanted, compose(&route).preimage(&origins.locations));
+        assert_eq!(origins.to_string().lines().nth(1), Some("humidity: 46..47"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn locations() -> Result<()> {
+        assert_eq!(parse_locations("10..20")?, IntervalSet::from(10..20));
+        assert_eq!(parse_locations("7")?, IntervalSet::from(7..8));
+        assert_eq!(
+            parse_locations("10..5").unwrap_err().to_string(),
+            "Locations 10..5 run backwards"
+        );
+        assert_eq!(
+            parse_locations(&u64::MAX.to_string())
+                .unwrap_err()
+                .to_string(),
+            "Location 18446744073709551615 is past the last one"
+        );
+        assert!(parse_locations("x..5").is_err());
 
         Ok(())
     }
@@ -190,13 +238,14 @@ humidity-to-location map:
 ";
 
         assert_eq!(parse_seeds(input)?, ("", vec![79..81, 55..58]));
+        assert!(parse_seeds("seeds: 99999999999999999999 1\n\n").is_err());
+        assert!(parse_seeds("seeds: 18446744073709551615 1\n\n").is_err());
 
         Ok(())
     }
 }
 
-#[allow(unused)]
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "seeds: 919339981 562444630 3366006921 67827214 1496677366 101156779 4140591657 5858311 2566406753 71724353 2721360939 35899538 383860877 424668759 3649554897 442182562 2846055542 49953829 2988140126 256306471
 
 seed-to-soil map:
@@ -404,7 +453,22 @@ humidity-to-location map:
 1647757140 2247246174 9396637
 2434490733 4247201303 1535992";
 
-    println!("5.2: {}", process(input)?);
+    let target = day5::target_from_param();
+    let (seeds, almanac) = mem::phase("parse", || parse(input))?;
+    let route = almanac.route(&target)?;
+    let composed = mem::phase("compose", || compose(&route));
+
+    if runner::explaining() {
+        runner::explain(&composed);
+    }
+
+    let locations = locations_from_param()?;
+
+    if let Some(locations) = locations.filter(|_| runner::explaining()) {
+        runner::explain(&Origins::new(locations, &seeds, &route));
+    }
+
+    let result = mem::phase("solve", || solve(&seeds, &composed))?;
 
-    Ok(())
+    Ok(result.to_string())
 }
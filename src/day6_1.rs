@@ -1,3 +1,5 @@
+use crate::mem;
+use crate::num::{self, Int};
 use anyhow::Result;
 use nom::{
     bytes::complete::tag,
@@ -42,37 +44,35 @@ fn parse_races(input: &str) -> Result<Vec<Race>> {
         .collect())
 }
 
-fn process_races(races: Vec<Race>) -> u32 {
-    let mut total_ways = 1;
+fn process_races<T: Int>(races: &[Race]) -> Result<T> {
+    let mut total_ways = T::ONE;
 
     for race in races {
-        let mut ways_to_win: Option<u32> = None;
+        let time_ms: T = num::cast(race.time_ms, "race time")?;
+        let distance_mm: T = num::cast(race.distance_mm, "race distance")?;
+        let mut ways_to_win: Option<T> = None;
+        let mut button_hold_time = T::ZERO;
 
-        for button_hold_time in 0..race.time_ms {
-            let remaining_time = race.time_ms - button_hold_time;
-            let distance = button_hold_time * remaining_time;
+        while button_hold_time < time_ms {
+            let remaining_time = num::sub(time_ms, button_hold_time, "remaining time")?;
+            let distance = num::mul(button_hold_time, remaining_time, "distance travelled")?;
 
-            if distance > race.distance_mm {
+            if distance > distance_mm {
                 if let Some(count) = ways_to_win {
-                    ways_to_win = Some(count + 1);
+                    ways_to_win = Some(num::add(count, T::ONE, "ways to win")?);
                 } else {
-                    ways_to_win = Some(1);
+                    ways_to_win = Some(T::ONE);
                 }
             }
+
+            button_hold_time = button_hold_time + T::ONE;
         }
 
         if let Some(count) = ways_to_win {
-            total_ways *= count;
+            total_ways = num::mul(total_ways, count, "product of ways to win")?;
         }
     }
 
-    total_ways
-}
-
-fn process(input: &str) -> Result<u32> {
-    let races = parse_races(input)?;
-    let total_ways = process_races(races);
-
     Ok(total_ways)
 }
 
@@ -107,7 +107,7 @@ Distance:  9  40  200";
     }
 
     #[test]
-    fn test_race_processing() {
+    fn test_race_processing() -> Result<()> {
         let input = vec![
             Race {
                 time_ms: 7,
@@ -123,17 +123,23 @@ Distance:  9  40  200";
             },
         ];
 
-        assert_eq!(process_races(input), 288);
+        assert_eq!(process_races::<u32>(&input)?, 288);
+
+        Ok(())
     }
 }
 
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "Time:        51     92     68     90
 Distance:   222   2031   1126   1225";
 
-    let result = process(input)?;
-
-    println!("6.1: {}", result);
+    let races = mem::phase("parse", || parse_races(input))?;
+    let result = mem::phase("solve", || {
+        num::widen(
+            || process_races::<u32>(&races),
+            || process_races::<u128>(&races),
+        )
+    })?;
 
-    Ok(())
+    Ok(result)
 }
@@ -1,3 +1,6 @@
+use crate::mem;
+use crate::num::{self, Int};
+use crate::progress::Progress;
 use anyhow::Result;
 use nom::{
     bytes::complete::tag,
@@ -45,26 +48,37 @@ fn parse_race(input: &str) -> Result<Race> {
     })
 }
 
-fn process_race(race: Race) -> u64 {
-    let mut ways_to_win = 0;
+/// Hold times tried between progress updates.
+const CHUNK: u64 = 1 << 20;
 
-    for button_hold_time in 0..race.time_ms {
-        let remaining_time = race.time_ms - button_hold_time;
-        let distance = button_hold_time * remaining_time;
+fn process_race<T: Int>(race: &Race) -> Result<T> {
+    let time_ms: T = num::cast(race.time_ms, "race time")?;
+    let distance_mm: T = num::cast(race.distance_mm, "race distance")?;
+    let progress = Progress::new("6.2", race.time_ms);
+    let mut ways_to_win = T::ZERO;
+    let mut button_hold_time = T::ZERO;
+    let mut tried = 0;
 
-        if distance > race.distance_mm {
-            ways_to_win += 1;
+    while button_hold_time < time_ms {
+        let remaining_time = num::sub(time_ms, button_hold_time, "remaining time")?;
+        let distance = num::mul(button_hold_time, remaining_time, "distance travelled")?;
+
+        if distance > distance_mm {
+            ways_to_win = num::add(ways_to_win, T::ONE, "ways to win")?;
         }
-    }
 
-    ways_to_win
-}
+        button_hold_time = button_hold_time + T::ONE;
+        tried += 1;
 
-fn process(input: &str) -> Result<u64> {
-    let race = parse_race(input)?;
-    let total_ways = process_race(race);
+        if tried % CHUNK == 0 {
+            progress.inc(CHUNK);
+        }
+    }
 
-    Ok(total_ways)
+    progress.inc(tried % CHUNK);
+    progress.finish();
+
+    Ok(ways_to_win)
 }
 
 #[cfg(test)]
@@ -88,24 +102,29 @@ Distance:  9  40  200";
     }
 
     #[test]
-    fn test_race_processing() {
+    fn test_race_processing() -> Result<()> {
         let input = Race {
             time_ms: 71530,
             distance_mm: 940200,
         };
 
-        assert_eq!(process_race(input), 71503);
+        assert_eq!(process_race::<u64>(&input)?, 71503);
+
+        Ok(())
     }
 }
 
-#[allow(unused)]
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "Time:        51     92     68     90
 Distance:   222   2031   1126   1225";
 
-    let result = process(input)?;
-
-    println!("6.2: {}", result);
+    let race = mem::phase("parse", || parse_race(input))?;
+    let result = mem::phase("solve", || {
+        num::widen(
+            || process_race::<u64>(&race),
+            || process_race::<u128>(&race),
+        )
+    })?;
 
-    Ok(())
+    Ok(result)
 }
@@ -1,3 +1,5 @@
+use crate::mem;
+use crate::num::{self, Int};
 use anyhow::Result;
 use nom::{
     branch::alt,
@@ -129,24 +131,35 @@ fn parse_games(input: &str) -> IResult<&str, Vec<(Cards, u32)>> {
 
 fn get_games(input: &str) -> Result<Vec<Game>> {
     let (_, parsed_games) = parse_games(input).map_err(|e| e.to_owned())?;
-    Ok(parsed_games
-        .into_iter()
-        .map(|p| Game::from_parsed(p))
-        .collect())
+    Ok(parsed_games.into_iter().map(Game::from_parsed).collect())
 }
 
-fn process(input: &str) -> Result<u32> {
-    let mut games = get_games(input)?;
-
+/// Orders games from weakest to strongest hand, so a game's rank is its position.
+fn ranked(mut games: Vec<Game>) -> Vec<Game> {
     games.sort_unstable_by_key(|g| g.hand);
 
-    let result = games.iter().enumerate().fold(0, |acc, (index, game)| {
-        acc + (game.bet * (index as u32 + 1))
-    });
+    games
+}
+
+/// Expects the games in rank order, as [`ranked`] leaves them.
+fn solve<T: Int>(games: &[Game]) -> Result<T> {
+    let mut result = T::ZERO;
+
+    for (index, game) in games.iter().enumerate() {
+        let rank = num::cast(index + 1, "rank")?;
+        let winnings = num::mul(num::cast(game.bet, "bet")?, rank, "winnings")?;
+
+        result = num::add(result, winnings, "total winnings")?;
+    }
 
     Ok(result)
 }
 
+#[cfg(test)]
+fn process<T: Int>(input: &str) -> Result<T> {
+    solve(&ranked(get_games(input)?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,7 +270,7 @@ KK677 28
 KTJJT 220
 QQQJA 483";
 
-        assert_eq!(process(input)?, 5905);
+        assert_eq!(process::<u32>(input)?, 5905);
 
         Ok(())
     }
@@ -274,8 +287,7 @@ QQQJA 483";
     }
 }
 
-#[allow(unused)]
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "486AA 252
 24544 330
 33TTT 540
@@ -1276,9 +1288,10 @@ Q7KQQ 515
 8QQ88 670
 38783 86
 6646T 32";
-    let result = process(input)?;
+    let games = mem::phase("parse", || get_games(input).map(ranked))?;
+    let result = mem::phase("solve", || {
+        num::widen(|| solve::<u32>(&games), || solve::<u128>(&games))
+    })?;
 
-    println!("7.2: {}", result);
-
-    Ok(())
+    Ok(result)
 }
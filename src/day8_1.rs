@@ -1,3 +1,5 @@
+use crate::graph;
+use crate::mem;
 use anyhow::Result;
 use nom::{
     branch::alt,
@@ -10,7 +12,7 @@ use nom::{
 };
 use std::collections::HashMap;
 
-#[derive(Debug, Hash, Eq, PartialEq)]
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
 struct Point(char, char, char);
 
 #[derive(Clone)]
@@ -73,25 +75,26 @@ fn map(input: &str) -> IResult<&str, Map> {
     ))
 }
 
-fn count_steps(map: &Map) -> usize {
-    let mut key = &Point::AAA;
-    let mut steps = 0;
+fn next(map: &Map, (key, step): &(Point, usize)) -> Vec<((Point, usize), u64)> {
+    let wp = map.forks.get(key).expect("value on key {key}");
 
-    while key != &Point::ZZZ {
-        let wp = map.forks.get(key).expect("value on key {key}");
-        let dir = &map.directions[steps % map.directions.len()];
+    let key = match map.directions[*step] {
+        Direction::Left => &wp.left,
+        Direction::Right => &wp.right,
+    };
 
-        key = match dir {
-            Direction::Left => &wp.left,
-            Direction::Right => &wp.right,
-        };
+    vec![((key.clone(), (step + 1) % map.directions.len()), 1)]
+}
 
-        steps += 1;
-    }
+fn count_steps(map: &Map) -> usize {
+    let graph = graph::from_fn(|state| next(map, state));
+    let path = graph::bfs_path(&graph, (Point::AAA, 0), |(key, _)| key == &Point::ZZZ)
+        .expect("a path to ZZZ");
 
-    steps
+    path.len() - 1
 }
 
+#[cfg(test)]
 fn process(input: &str) -> Result<usize> {
     let (_, map) = map(input).map_err(|e| e.to_owned())?;
     let steps = count_steps(&map);
@@ -131,7 +134,7 @@ ZZZ = (ZZZ, ZZZ)";
     }
 }
 
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "LLRLRRLLRLRRLLRLRRLRRRLRLRLRRRLLRLRRRLRLRRRLRLRLLLRRLRLRLLRLRRLRRRLRRRLLRRLRLRRRLRRLRRRLRLLRRLRRRLRRRLRRLRLRRLLLRLRLLRRRLRRLLRLRLRRLLRLRRLLRLRRLRRLLRRRLRLRLRRRLLRRRLRRLRRRLRRRLRLRRRLRRLLLRRRLRLLLRRRLRLLRLLRRRLLRRLRRRLRRRLRLLRLRLRRRLLRRLRRRLRRLRLLRRRLRRLRRRLRRRLRRRLRLRRRLRRRLRLRRRR
 
 XGS = (FDM, XCS)
@@ -928,9 +931,8 @@ SMR = (QLS, QLS)
 BMQ = (VRX, XMK)
 FNB = (SNS, HRG)
 BKX = (TFD, JVB)";
-    let result = process(input)?;
-
-    println!("8.1: {}", result);
+    let (_, map) = mem::phase("parse", || map(input).map_err(|e| e.to_owned()))?;
+    let result = mem::phase("solve", || count_steps(&map));
 
-    Ok(())
+    Ok(result.to_string())
 }
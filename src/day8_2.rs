@@ -1,3 +1,6 @@
+use crate::graph;
+use crate::mem;
+use crate::num::{self, Int};
 use anyhow::Result;
 use nom::{
     branch::alt,
@@ -10,7 +13,7 @@ use nom::{
 };
 use std::collections::HashMap;
 
-#[derive(Debug, Hash, Eq, PartialEq)]
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
 struct Point(char, char, char);
 
 #[derive(Clone)]
@@ -68,18 +71,16 @@ fn map(input: &str) -> IResult<&str, Map> {
     ))
 }
 
-fn greatest_common_divisor(mut a: usize, mut b: usize) -> usize {
+fn greatest_common_divisor<T: Int>(mut a: T, mut b: T) -> T {
     if a == b {
         return a;
     }
 
     if b > a {
-        let temp = a;
-        a = b;
-        b = temp;
+        std::mem::swap(&mut a, &mut b);
     }
 
-    while b > 0 {
+    while b > T::ZERO {
         let temp = a;
         a = b;
         b = temp % b;
@@ -88,43 +89,45 @@ fn greatest_common_divisor(mut a: usize, mut b: usize) -> usize {
     a
 }
 
-fn lowest_common_multiplier(a: usize, b: usize) -> usize {
-    a * (b / greatest_common_divisor(a, b))
+fn lowest_common_multiplier<T: Int>(a: T, b: T) -> Result<T> {
+    num::mul(
+        a,
+        b / greatest_common_divisor(a, b),
+        "lowest common multiplier",
+    )
+}
+
+fn next(map: &Map, (key, step): &(Point, usize)) -> Vec<((Point, usize), u64)> {
+    let wp = map.forks.get(key).expect("valid key");
+
+    let key = match map.directions[*step] {
+        Direction::Left => &wp.left,
+        Direction::Right => &wp.right,
+    };
+
+    vec![((key.clone(), (step + 1) % map.directions.len()), 1)]
 }
 
-fn count_steps(map: &Map) -> usize {
+fn count_steps<T: Int>(map: &Map) -> Result<T> {
+    let graph = graph::from_fn(|state| next(map, state));
+
     map.forks
         .keys()
         .filter(|key| key.2 == 'A')
         .map(|start_key| {
-            let mut key = start_key;
-            map.directions
-                .iter()
-                .cycle()
-                .enumerate()
-                .find_map(|(index, dir)| {
-                    let wp = map.forks.get(key).expect("valid key");
-
-                    key = match dir {
-                        Direction::Left => &wp.left,
-                        Direction::Right => &wp.right,
-                    };
-
-                    if key.2 == 'Z' {
-                        Some(index + 1)
-                    } else {
-                        None
-                    }
-                })
-                .expect("valid target")
+            let path = graph::bfs_path(&graph, (start_key.clone(), 0), |(key, _)| key.2 == 'Z')
+                .expect("valid target");
+
+            num::cast(path.len() - 1, "steps")
         })
-        .reduce(lowest_common_multiplier)
+        .reduce(|a, b| lowest_common_multiplier(a?, b?))
         .expect("at least one key")
 }
 
-fn process(input: &str) -> Result<usize> {
+#[cfg(test)]
+fn process<T: Int>(input: &str) -> Result<T> {
     let (_, map) = map(input).map_err(|e| e.to_owned())?;
-    let steps = count_steps(&map);
+    let steps = count_steps(&map)?;
 
     Ok(steps)
 }
@@ -146,12 +149,11 @@ mod tests {
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
 
-        assert_eq!(process(input).unwrap(), 6);
+        assert_eq!(process::<usize>(input).unwrap(), 6);
     }
 }
 
-#[allow(unused)]
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "LLRLRRLLRLRRLLRLRRLRRRLRLRLRRRLLRLRRRLRLRRRLRLRLLLRRLRLRLLRLRRLRRRLRRRLLRRLRLRRRLRRLRRRLRLLRRLRRRLRRRLRRLRLRRLLLRLRLLRRRLRRLLRLRLRRLLRLRRLLRLRRLRRLLRRRLRLRLRRRLLRRRLRRLRRRLRRRLRLRRRLRRLLLRRRLRLLLRRRLRLLRLLRRRLLRRLRRRLRRRLRLLRLRLRRRLLRRLRRRLRRLRLLRRRLRRLRRRLRRRLRRRLRLRRRLRRRLRLRRRR
 
 XGS = (FDM, XCS)
@@ -948,9 +950,10 @@ SMR = (QLS, QLS)
 BMQ = (VRX, XMK)
 FNB = (SNS, HRG)
 BKX = (TFD, JVB)";
-    let result = process(input)?;
-
-    println!("8.2: {}", result);
+    let (_, map) = mem::phase("parse", || map(input).map_err(|e| e.to_owned()))?;
+    let result = mem::phase("solve", || {
+        num::widen(|| count_steps::<usize>(&map), || count_steps::<u128>(&map))
+    })?;
 
-    Ok(())
+    Ok(result)
 }
@@ -1,3 +1,5 @@
+use crate::mem;
+use crate::num::{self, Int};
 use anyhow::Result;
 use nom::{
     character::complete::{i32, line_ending, space1},
@@ -9,28 +11,44 @@ fn parse(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
     separated_list1(line_ending, separated_list1(space1, i32))(input)
 }
 
-fn crunch(seq: Vec<i32>) -> i32 {
+fn crunch<T: Int>(seq: &[i32]) -> Result<T> {
+    let seq = seq
+        .iter()
+        .map(|n| num::cast(*n, "sequence value"))
+        .collect::<Result<Vec<T>>>()?;
     let mut layers = vec![seq.clone()];
     let mut current = seq;
 
-    while !current.iter().all(|n| *n == 0) {
+    while !current.iter().all(|n| *n == T::ZERO) {
         let mut next = vec![];
 
-        for i in 0..current.len() - 1 {
-            next.push(current[i + 1] - current[i]);
+        for pair in current.windows(2) {
+            next.push(num::sub(pair[1], pair[0], "difference")?);
         }
 
         current = next.clone();
         layers.push(next);
     }
 
-    layers.iter().rfold(0, |a, b| b[0] - a)
+    layers
+        .iter()
+        .try_rfold(T::ZERO, |a, b| num::sub(b[0], a, "extrapolated value"))
 }
 
-fn process(input: &str) -> Result<i32> {
+fn solve<T: Int>(sequences: &[Vec<i32>]) -> Result<T> {
+    let values = sequences
+        .iter()
+        .map(|seq| crunch(seq))
+        .collect::<Result<Vec<T>>>()?;
+
+    num::sum(values, "sum of extrapolated values")
+}
+
+#[cfg(test)]
+fn process<T: Int>(input: &str) -> Result<T> {
     let (_, sequences) = parse(input).map_err(|e| e.to_owned())?;
 
-    Ok(sequences.into_iter().map(crunch).sum())
+    solve(&sequences)
 }
 
 #[cfg(test)]
@@ -43,14 +61,13 @@ mod tests {
 1 3 6 10 15 21
 10 13 16 21 30 45";
 
-        assert_eq!(process(input)?, 2);
+        assert_eq!(process::<i32>(input)?, 2);
 
         Ok(())
     }
 }
 
-#[allow(unused)]
-pub fn main() -> Result<()> {
+pub fn main() -> Result<String> {
     let input = "10 37 82 150 246 389 638 1129 2122 4057 7618 13804 24006 40089 64478 100247 151210 222013 318226 446434 614326
 4 11 32 93 230 496 975 1798 3150 5243 8219 11979 16093 20384 27760 52853 143831 431834 1235397 3270284 8055403
 26 49 90 163 288 500 869 1531 2730 4871 8584 14799 24832 40482 64139 98903 148714 218493 314294 443467 614832
@@ -251,9 +268,10 @@ pub fn main() -> Result<()> {
 -7 -3 9 44 136 346 781 1639 3303 6525 12785 25007 49000 96309 189672 373150 730754 1421573 2750017 5316307 10355491
 17 28 58 129 284 596 1172 2157 3745 6202 9900 15351 23216 34246 49090 67879 89465 110160 121782 108773 44108
 4 10 18 29 44 65 100 179 390 951 2348 5598 12758 27924 59182 122318 247584 491440 955886 1818627 3377640";
-    let result = process(input)?;
-
-    println!("9.2: {}", result);
+    let (_, sequences) = mem::phase("parse", || parse(input).map_err(|e| e.to_owned()))?;
+    let result = mem::phase("solve", || {
+        num::widen(|| solve::<i32>(&sequences), || solve::<i128>(&sequences))
+    })?;
 
-    Ok(())
+    Ok(result)
 }
@@ -0,0 +1,517 @@
+use anyhow::{bail, Result};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Anything that can list the weighted edges leaving a node.
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    fn neighbours(&self, node: &Self::Node) -> Vec<(Self::Node, u64)>;
+}
+
+#[derive(Debug, Clone)]
+pub struct AdjacencyList<N> {
+    edges: HashMap<N, Vec<(N, u64)>>,
+}
+
+impl<N: Clone + Eq + Hash> AdjacencyList<N> {
+    pub fn new() -> AdjacencyList<N> {
+        AdjacencyList {
+            edges: HashMap::new(),
+        }
+    }
+
+    pub fn add_node(&mut self, node: N) {
+        self.edges.entry(node).or_default();
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N, cost: u64) {
+        self.add_node(to.clone());
+        self.edges.entry(from).or_default().push((to, cost));
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn add_undirected_edge(&mut self, a: N, b: N, cost: u64) {
+        self.add_edge(a.clone(), b.clone(), cost);
+        self.add_edge(b, a, cost);
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn nodes(&self) -> Vec<N> {
+        self.edges.keys().cloned().collect()
+    }
+}
+
+impl<N: Clone + Eq + Hash> Default for AdjacencyList<N> {
+    fn default() -> Self {
+        AdjacencyList::new()
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph for AdjacencyList<N> {
+    type Node = N;
+
+    fn neighbours(&self, node: &N) -> Vec<(N, u64)> {
+        self.edges.get(node).cloned().unwrap_or_default()
+    }
+}
+
+/// A `width` by `height` grid of `(x, y)` cells where each cell may step to its four orthogonal
+/// neighbours. `connects` decides whether a step is allowed and what it costs.
+pub struct Grid<F> {
+    pub width: usize,
+    pub height: usize,
+    connects: F,
+}
+
+impl<F> Grid<F>
+where
+    F: Fn((usize, usize), (usize, usize)) -> Option<u64>,
+{
+    pub fn new(width: usize, height: usize, connects: F) -> Grid<F> {
+        Grid {
+            width,
+            height,
+            connects,
+        }
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn nodes(&self) -> Vec<(usize, usize)> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .collect()
+    }
+}
+
+impl<F> Graph for Grid<F>
+where
+    F: Fn((usize, usize), (usize, usize)) -> Option<u64>,
+{
+    type Node = (usize, usize);
+
+    fn neighbours(&self, &(x, y): &(usize, usize)) -> Vec<((usize, usize), u64)> {
+        let mut neighbours = vec![];
+
+        if y > 0 {
+            neighbours.push((x, y - 1));
+        }
+        if y + 1 < self.height {
+            neighbours.push((x, y + 1));
+        }
+        if x > 0 {
+            neighbours.push((x - 1, y));
+        }
+        if x + 1 < self.width {
+            neighbours.push((x + 1, y));
+        }
+
+        neighbours
+            .into_iter()
+            .filter_map(|next| (self.connects)((x, y), next).map(|cost| (next, cost)))
+            .collect()
+    }
+}
+
+/// A graph whose edges are computed on demand, for state spaces too large to build up front.
+pub struct FromFn<N, F> {
+    neighbours: F,
+    node: PhantomData<N>,
+}
+
+pub fn from_fn<N, F>(neighbours: F) -> FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    FromFn {
+        neighbours,
+        node: PhantomData,
+    }
+}
+
+impl<N, F> Graph for FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    type Node = N;
+
+    fn neighbours(&self, node: &N) -> Vec<(N, u64)> {
+        (self.neighbours)(node)
+    }
+}
+
+fn unwind<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, mut node: N) -> Vec<N> {
+    let mut path = vec![node.clone()];
+
+    while let Some(parent) = parents.get(&node) {
+        path.push(parent.clone());
+        node = parent.clone();
+    }
+
+    path.reverse();
+    path
+}
+
+/// Number of edges from `start` to every reachable node, ignoring edge costs.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn bfs<G: Graph>(graph: &G, start: G::Node) -> HashMap<G::Node, usize> {
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+
+        for (next, _) in graph.neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// The path with the fewest edges from `start` to the first node satisfying `is_goal`,
+/// both ends included.
+pub fn bfs_path<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+) -> Option<Vec<G::Node>> {
+    let mut parents = HashMap::new();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(unwind(&parents, node));
+        }
+
+        for (next, _) in graph.neighbours(&node) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Nodes reachable from `start` in depth-first pre-order.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn dfs<G: Graph>(graph: &G, start: G::Node) -> Vec<G::Node> {
+    let mut order = vec![];
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+
+        let mut neighbours = graph.neighbours(&node);
+        neighbours.reverse();
+
+        stack.extend(
+            neighbours
+                .into_iter()
+                .map(|(next, _)| next)
+                .filter(|next| !seen.contains(next)),
+        );
+        order.push(node);
+    }
+
+    order
+}
+
+/// Cheapest total cost from `start` to every reachable node.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn dijkstra<G: Graph>(graph: &G, start: G::Node) -> HashMap<G::Node, u64> {
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut nodes = vec![start];
+    let mut heap = BinaryHeap::from([Reverse((0, 0))]);
+
+    while let Some(Reverse((cost, index))) = heap.pop() {
+        let node = nodes[index].clone();
+
+        if costs[&node] < cost {
+            continue;
+        }
+
+        for (next, step) in graph.neighbours(&node) {
+            let next_cost = cost + step;
+
+            if costs.get(&next).is_none_or(|c| next_cost < *c) {
+                costs.insert(next.clone(), next_cost);
+                heap.push(Reverse((next_cost, nodes.len())));
+                nodes.push(next);
+            }
+        }
+    }
+
+    costs
+}
+
+/// Cheapest path from `start` to a node satisfying `is_goal`. `heuristic` must never overestimate
+/// the remaining cost, otherwise the result may not be optimal.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn astar<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> u64,
+) -> Option<(u64, Vec<G::Node>)> {
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut parents = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut nodes = vec![start];
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let node = nodes[index].clone();
+
+        if costs[&node] < cost {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return Some((cost, unwind(&parents, node)));
+        }
+
+        for (next, step) in graph.neighbours(&node) {
+            let next_cost = cost + step;
+
+            if costs.get(&next).is_none_or(|c| next_cost < *c) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(Reverse((
+                    next_cost + heuristic(&next),
+                    next_cost,
+                    nodes.len(),
+                )));
+                nodes.push(next);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn shortest_path<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl Fn(&G::Node) -> bool,
+) -> Option<(u64, Vec<G::Node>)> {
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// Groups `nodes` by reachability. Edges are expected to be symmetric.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn connected_components<G: Graph>(graph: &G, nodes: &[G::Node]) -> Vec<Vec<G::Node>> {
+    let mut seen = HashSet::new();
+    let mut components = vec![];
+
+    for node in nodes {
+        if seen.contains(node) {
+            continue;
+        }
+
+        let component = dfs(graph, node.clone());
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+
+    components
+}
+
+/// A directed cycle reachable from `start`, if there is one.
+pub fn find_cycle<G: Graph>(graph: &G, start: G::Node) -> Option<Vec<G::Node>> {
+    let mut done = HashSet::new();
+    let mut path: Vec<G::Node> = vec![];
+    let mut stack = vec![(start, 0)];
+
+    while let Some((node, depth)) = stack.pop() {
+        while path.len() > depth {
+            done.insert(path.pop().expect("non-empty path"));
+        }
+
+        if let Some(index) = path.iter().position(|n| n == &node) {
+            return Some(path[index..].to_vec());
+        }
+
+        if done.contains(&node) {
+            continue;
+        }
+
+        path.push(node.clone());
+        stack.extend(
+            graph
+                .neighbours(&node)
+                .into_iter()
+                .map(|(next, _)| (next, depth + 1)),
+        );
+    }
+
+    None
+}
+
+/// A cycle of at least three nodes passing through `start`, treating edges as undirected.
+pub fn cycle_through<G: Graph>(graph: &G, start: G::Node) -> Option<Vec<G::Node>> {
+    let mut seen = HashSet::from([start.clone()]);
+    let mut path = vec![start.clone()];
+    let mut stack = vec![graph.neighbours(&start)];
+
+    while let Some(pending) = stack.last_mut() {
+        let Some((next, _)) = pending.pop() else {
+            stack.pop();
+            path.pop();
+            continue;
+        };
+
+        if next == start && path.len() >= 3 {
+            return Some(path);
+        }
+
+        if seen.insert(next.clone()) {
+            stack.push(graph.neighbours(&next));
+            path.push(next);
+        }
+    }
+
+    None
+}
+
+/// Orders `nodes` so that every edge points forwards, failing if the graph has a cycle.
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn topological_sort<G: Graph>(graph: &G, nodes: &[G::Node]) -> Result<Vec<G::Node>> {
+    let mut incoming: HashMap<G::Node, usize> = nodes.iter().map(|n| (n.clone(), 0)).collect();
+
+    for node in nodes {
+        for (next, _) in graph.neighbours(node) {
+            *incoming.entry(next).or_insert(0) += 1;
+        }
+    }
+
+    let mut queue: VecDeque<G::Node> = nodes
+        .iter()
+        .filter(|n| incoming[*n] == 0)
+        .cloned()
+        .collect();
+    let mut order = vec![];
+
+    while let Some(node) = queue.pop_front() {
+        for (next, _) in graph.neighbours(&node) {
+            let count = incoming.get_mut(&next).expect("counted node");
+            *count -= 1;
+
+            if *count == 0 {
+                queue.push_back(next);
+            }
+        }
+
+        order.push(node);
+    }
+
+    if order.len() < incoming.len() {
+        bail!(
+            "graph contains a cycle through {} nodes",
+            incoming.len() - order.len()
+        );
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diamond() -> AdjacencyList<char> {
+        let mut graph = AdjacencyList::new();
+        graph.add_edge('a', 'b', 1);
+        graph.add_edge('a', 'c', 4);
+        graph.add_edge('b', 'c', 2);
+        graph.add_edge('b', 'd', 6);
+        graph.add_edge('c', 'd', 3);
+
+        graph
+    }
+
+    #[test]
+    fn breadth_and_depth_first() {
+        let graph = diamond();
+
+        assert_eq!(bfs(&graph, 'a')[&'d'], 2);
+        assert_eq!(bfs_path(&graph, 'a', |n| *n == 'd').unwrap().len(), 3);
+        assert_eq!(dfs(&graph, 'a'), vec!['a', 'b', 'c', 'd']);
+        assert_eq!(bfs_path(&graph, 'd', |n| *n == 'a'), None);
+    }
+
+    #[test]
+    fn weighted_paths() {
+        let graph = diamond();
+
+        assert_eq!(dijkstra(&graph, 'a')[&'d'], 6);
+        assert_eq!(
+            shortest_path(&graph, 'a', |n| *n == 'd'),
+            Some((6, vec!['a', 'b', 'c', 'd']))
+        );
+    }
+
+    #[test]
+    fn astar_on_grid() {
+        let walls = ["....", ".##.", "...#", "#..."];
+        let open = |(x, y): (usize, usize)| walls[y].as_bytes()[x] == b'.';
+        let grid = Grid::new(4, 4, |from, to| (open(from) && open(to)).then_some(1));
+
+        let (cost, path) = astar(
+            &grid,
+            (0, 0),
+            |n| *n == (3, 3),
+            |&(x, y)| (3 - x + 3 - y) as u64,
+        )
+        .unwrap();
+
+        assert_eq!(cost, 6);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(3, 3)));
+        assert_eq!(connected_components(&grid, &grid.nodes()).len(), 5);
+    }
+
+    #[test]
+    fn cycles_and_ordering() -> Result<()> {
+        let mut graph = diamond();
+
+        assert_eq!(find_cycle(&graph, 'a'), None);
+        assert_eq!(
+            topological_sort(&graph, &graph.nodes())?,
+            vec!['a', 'b', 'c', 'd']
+        );
+
+        graph.add_edge('d', 'b', 1);
+
+        assert!(find_cycle(&graph, 'a').is_some());
+        assert!(topological_sort(&graph, &graph.nodes()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn undirected_cycle() {
+        let mut graph = AdjacencyList::new();
+        graph.add_undirected_edge(0, 1, 1);
+        graph.add_undirected_edge(1, 2, 1);
+        graph.add_undirected_edge(2, 3, 1);
+        graph.add_undirected_edge(3, 0, 1);
+        graph.add_undirected_edge(0, 4, 1);
+
+        assert_eq!(cycle_through(&graph, 0).map(|c| c.len()), Some(4));
+        assert_eq!(cycle_through(&graph, 4), None);
+    }
+}
@@ -0,0 +1,566 @@
+use serde::Serialize;
+use std::fmt::Display;
+use std::ops::Range;
+
+/// A set of `u64` values stored as sorted, disjoint, non-adjacent half-open ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct IntervalSet {
+    ranges: Vec<Range<u64>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet { ranges: vec![] }
+    }
+
+    pub fn ranges(&self) -> &[Range<u64>] {
+        &self.ranges
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of values in the set, not the number of ranges.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn count(&self) -> u64 {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn max(&self) -> Option<u64> {
+        self.ranges.last().map(|r| r.end - 1)
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn contains(&self, value: u64) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= value);
+
+        self.ranges.get(index).is_some_and(|r| r.start <= value)
+    }
+
+    pub fn insert(&mut self, range: Range<u64>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // Everything that overlaps or touches `range` gets merged into it.
+        let from = self.ranges.partition_point(|r| r.end < range.start);
+        let to = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = if from < to {
+            self.ranges[from].start.min(range.start)..self.ranges[to - 1].end.max(range.end)
+        } else {
+            range
+        };
+
+        self.ranges.splice(from..to, [merged]);
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = self.clone();
+
+        for range in other.ranges.iter() {
+            result.insert(range.clone());
+        }
+
+        result
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = vec![];
+        let (mut a, mut b) = (0, 0);
+
+        while a < self.ranges.len() && b < other.ranges.len() {
+            let left = &self.ranges[a];
+            let right = &other.ranges[b];
+
+            let start = left.start.max(right.start);
+            let end = left.end.min(right.end);
+
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            if left.end < right.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = vec![];
+        let mut b = 0;
+
+        for range in self.ranges.iter() {
+            let mut start = range.start;
+
+            while b < other.ranges.len() && other.ranges[b].end <= start {
+                b += 1;
+            }
+
+            let mut cursor = b;
+
+            while cursor < other.ranges.len() && other.ranges[cursor].start < range.end {
+                let cut = &other.ranges[cursor];
+
+                if cut.start > start {
+                    ranges.push(start..cut.start);
+                }
+
+                start = start.max(cut.end);
+                cursor += 1;
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    /// Cuts the ranges of the set at every given point that falls strictly inside one of them.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn split_at(&self, points: &[u64]) -> Vec<Range<u64>> {
+        let mut points = points.to_vec();
+        points.sort_unstable();
+        points.dedup();
+
+        let mut pieces = vec![];
+
+        for range in self.ranges.iter() {
+            let mut start = range.start;
+            let from = points.partition_point(|p| *p <= range.start);
+
+            for point in points[from..].iter().take_while(|p| **p < range.end) {
+                pieces.push(start..*point);
+                start = *point;
+            }
+
+            pieces.push(start..range.end);
+        }
+
+        pieces
+    }
+
+    /// Moves every value by `offset`, or returns `None` if any value would leave the `u64` domain.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn shift(&self, offset: i64) -> Option<IntervalSet> {
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|r| {
+                let start = r.start.checked_add_signed(offset)?;
+                let end = r.end.checked_add_signed(offset)?;
+
+                Some(start..end)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(IntervalSet { ranges })
+    }
+}
+
+impl FromIterator<Range<u64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range<u64>>>(iter: I) -> IntervalSet {
+        let mut set = IntervalSet::new();
+
+        for range in iter {
+            set.insert(range);
+        }
+
+        set
+    }
+}
+
+impl From<Range<u64>> for IntervalSet {
+    fn from(range: Range<u64>) -> IntervalSet {
+        IntervalSet::from_iter([range])
+    }
+}
+
+impl Display for IntervalSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ranges.is_empty() {
+            return write!(f, "nothing");
+        }
+
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|r| format!("{}..{}", r.start, r.end))
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", ranges.join(", "))
+    }
+}
+
+/// A source range that is translated onto a destination starting at `dest_start`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Segment {
+    pub src: Range<u64>,
+    pub dest_start: u64,
+}
+
+impl Segment {
+    pub fn apply(&self, value: u64) -> u64 {
+        value - self.src.start + self.dest_start
+    }
+}
+
+/// A piecewise-linear function over `u64`: values inside a segment are translated,
+/// everything else maps onto itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct RangeMap {
+    segments: Vec<Segment>,
+}
+
+impl RangeMap {
+    pub fn new() -> RangeMap {
+        RangeMap { segments: vec![] }
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Adds a segment of `len` values from `src_start` to `dest_start`. Values already covered by
+    /// an earlier segment keep their mapping, so the first segment to claim a value wins.
+    pub fn insert(&mut self, src_start: u64, dest_start: u64, len: u64) {
+        let src = IntervalSet::from(src_start..src_start + len);
+        let covered = self
+            .segments
+            .iter()
+            .map(|s| s.src.clone())
+            .collect::<IntervalSet>();
+
+        for piece in src.difference(&covered).ranges() {
+            let segment = Segment {
+                src: piece.clone(),
+                dest_start: dest_start + (piece.start - src_start),
+            };
+            let index = self
+                .segments
+                .partition_point(|s| s.src.start < segment.src.start);
+
+            self.segments.insert(index, segment);
+        }
+    }
+
+    pub fn apply(&self, value: u64) -> u64 {
+        let index = self.segments.partition_point(|s| s.src.end <= value);
+
+        match self.segments.get(index) {
+            Some(segment) if segment.src.start <= value => segment.apply(value),
+            _ => value,
+        }
+    }
+
+    /// Maps a whole set at once by splitting it at segment boundaries.
+    pub fn apply_set(&self, set: &IntervalSet) -> IntervalSet {
+        let mut result = IntervalSet::new();
+
+        for range in set.ranges() {
+            let mut cursor = range.start;
+            let from = self.segments.partition_point(|s| s.src.end <= range.start);
+
+            for segment in self.segments[from..]
+                .iter()
+                .take_while(|s| s.src.start < range.end)
+            {
+                if cursor < segment.src.start {
+                    result.insert(cursor..segment.src.start);
+                }
+
+                let start = cursor.max(segment.src.start);
+                let end = range.end.min(segment.src.end);
+
+                result.insert(segment.apply(start)..segment.apply(end - 1) + 1);
+                cursor = end;
+            }
+
+            if cursor < range.end {
+                result.insert(cursor..range.end);
+            }
+        }
+
+        result
+    }
+
+    /// Every value that maps into `set`, found by running each piece of the map backwards.
+    pub fn preimage(&self, set: &IntervalSet) -> IntervalSet {
+        let mut result = IntervalSet::new();
+
+        for piece in self.pieces(0..u64::MAX) {
+            let image = piece.dest_start..piece.apply(piece.src.end - 1) + 1;
+
+            for hit in set.intersection(&IntervalSet::from(image)).ranges() {
+                let start = piece.src.start + (hit.start - piece.dest_start);
+
+                result.insert(start..start + (hit.end - hit.start));
+            }
+        }
+
+        result
+    }
+
+    /// Covers `range` with the segments that overlap it, cut to fit, and identity segments for
+    /// the gaps between them.
+    fn pieces(&self, range: Range<u64>) -> Vec<Segment> {
+        let mut pieces = vec![];
+        let mut cursor = range.start;
+        let from = self.segments.partition_point(|s| s.src.end <= range.start);
+
+        for segment in self.segments[from..]
+            .iter()
+            .take_while(|s| s.src.start < range.end)
+        {
+            if cursor < segment.src.start {
+                pieces.push(Segment {
+                    src: cursor..segment.src.start,
+                    dest_start: cursor,
+                });
+            }
+
+            let start = cursor.max(segment.src.start);
+            let end = range.end.min(segment.src.end);
+
+            pieces.push(Segment {
+                src: start..end,
+                dest_start: segment.apply(start),
+            });
+            cursor = end;
+        }
+
+        if cursor < range.end {
+            pieces.push(Segment {
+                src: cursor..range.end,
+                dest_start: cursor,
+            });
+        }
+
+        pieces
+    }
+
+    /// The map that applies `self` and then `next`. It is normalised: identity pieces are left
+    /// out and touching pieces with the same offset are joined, so equal functions compare equal.
+    pub fn then(&self, next: &RangeMap) -> RangeMap {
+        let mut composed = RangeMap::new();
+
+        for piece in self.pieces(0..u64::MAX) {
+            let image = piece.dest_start..piece.apply(piece.src.end - 1) + 1;
+
+            for mapped in next.pieces(image.clone()) {
+                let src_start = piece.src.start + (mapped.src.start - image.start);
+
+                composed.push(Segment {
+                    src: src_start..src_start + (mapped.src.end - mapped.src.start),
+                    dest_start: mapped.dest_start,
+                });
+            }
+        }
+
+        composed
+    }
+
+    /// Appends a segment starting at or after the last one, keeping the map normalised.
+    fn push(&mut self, segment: Segment) {
+        if segment.dest_start == segment.src.start {
+            return;
+        }
+
+        match self.segments.last_mut() {
+            Some(last)
+                if last.src.end == segment.src.start
+                    && last.apply(last.src.end - 1) + 1 == segment.dest_start =>
+            {
+                last.src.end = segment.src.end;
+            }
+            _ => self.segments.push(segment),
+        }
+    }
+}
+
+impl Display for RangeMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for segment in self.segments.iter() {
+            let offset = segment.dest_start as i128 - segment.src.start as i128;
+
+            writeln!(
+                f,
+                "{}..{} -> {}..{} ({:+})",
+                segment.src.start,
+                segment.src.end,
+                segment.dest_start,
+                segment.apply(segment.src.end - 1) + 1,
+                offset
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<u64>]) -> IntervalSet {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn insert_merges_overlapping_and_adjacent() {
+        let set = set(&[10..20, 30..40, 20..25, 5..11, 50..50]);
+
+        assert_eq!(set.ranges(), &[5..25, 30..40]);
+        assert_eq!(set.count(), 30);
+        assert!(set.contains(24));
+        assert!(!set.contains(25));
+        assert_eq!(set.min(), Some(5));
+        assert_eq!(set.max(), Some(39));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[0..10, 20..30]);
+        let b = IntervalSet::from(5..25);
+
+        assert_eq!(a.union(&b), IntervalSet::from(0..30));
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..30]);
+        assert_eq!(b.difference(&a), IntervalSet::from(10..20));
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn split_and_shift() {
+        let a = set(&[0..10, 20..30]);
+
+        assert_eq!(
+            a.split_at(&[25, 5, 10, 0]),
+            vec![0..5, 5..10, 20..25, 25..30]
+        );
+        assert_eq!(a.shift(5), Some(set(&[5..15, 25..35])));
+        assert_eq!(a.shift(-5), None);
+        assert_eq!(
+            IntervalSet::from(10..20).shift(-10),
+            Some(IntervalSet::from(0..10))
+        );
+        assert_eq!(IntervalSet::from(u64::MAX - 1..u64::MAX).shift(2), None);
+    }
+
+    #[test]
+    fn range_map_first_segment_wins() {
+        let mut map = RangeMap::new();
+        map.insert(98, 50, 2);
+        map.insert(50, 52, 50);
+
+        assert_eq!(map.apply(79), 81);
+        assert_eq!(map.apply(98), 50);
+        assert_eq!(map.apply(99), 51);
+        assert_eq!(map.apply(100), 100);
+        assert_eq!(map.segments().len(), 2);
+    }
+
+    #[test]
+    fn range_map_apply_set_matches_points() {
+        let mut map = RangeMap::new();
+        map.insert(98, 50, 2);
+        map.insert(50, 52, 48);
+
+        let seeds = set(&[40..60, 95..105]);
+        let mapped = map.apply_set(&seeds);
+        let expected = seeds
+            .ranges()
+            .iter()
+            .flat_map(|r| r.clone())
+            .map(|seed| map.apply(seed)..map.apply(seed) + 1)
+            .collect::<IntervalSet>();
+
+        assert_eq!(mapped, expected);
+        assert_eq!(mapped.count(), seeds.count());
+    }
+
+    #[test]
+    fn composition_matches_applying_in_turn() {
+        let mut first = RangeMap::new();
+        first.insert(98, 50, 2);
+        first.insert(50, 52, 48);
+
+        let mut second = RangeMap::new();
+        second.insert(15, 0, 37);
+        second.insert(52, 37, 2);
+        second.insert(0, 39, 15);
+
+        let composed = first.then(&second);
+
+        for value in 0..120 {
+            assert_eq!(composed.apply(value), second.apply(first.apply(value)));
+        }
+
+        let seeds = set(&[0..30, 45..110]);
+
+        assert_eq!(
+            composed.apply_set(&seeds),
+            second.apply_set(&first.apply_set(&seeds))
+        );
+        assert_eq!(RangeMap::new().then(&first), first);
+        assert_eq!(first.then(&RangeMap::new()), first);
+        assert_eq!(
+            composed.to_string().lines().next(),
+            Some("0..15 -> 39..54 (+39)")
+        );
+    }
+
+    #[test]
+    fn composition_is_normalised() {
+        let mut swap = RangeMap::new();
+        swap.insert(10, 20, 10);
+        swap.insert(20, 10, 10);
+
+        let mut back = RangeMap::new();
+        back.insert(20, 10, 5);
+        back.insert(25, 15, 5);
+        back.insert(10, 20, 10);
+
+        let mut joined = RangeMap::new();
+        joined.insert(0, 100, 5);
+        joined.insert(5, 105, 5);
+
+        assert!(swap.then(&back).segments().is_empty());
+        assert_eq!(joined.then(&RangeMap::new()).segments().len(), 1);
+    }
+
+    #[test]
+    fn preimage_matches_points() {
+        let mut map = RangeMap::new();
+        map.insert(98, 50, 2);
+        map.insert(50, 52, 48);
+
+        let locations = set(&[49..53, 98..101]);
+        let preimage = map.preimage(&locations);
+        let expected = (0..200)
+            .filter(|&value| locations.contains(map.apply(value)))
+            .map(|value| value..value + 1)
+            .collect::<IntervalSet>();
+
+        assert_eq!(preimage, expected);
+        assert_eq!(preimage.to_string(), "49..51, 96..101");
+        assert_eq!(IntervalSet::new().to_string(), "nothing");
+    }
+}
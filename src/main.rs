@@ -1,14 +1,20 @@
+// Every day keeps its `main` with the puzzle input below its tests.
+#![allow(clippy::items_after_test_module)]
+
 use anyhow::Result;
 
+mod day1;
 mod day10_1;
-mod day1_1;
-mod day1_2;
+mod day2;
 mod day2_1;
 mod day2_2;
+mod day3;
 mod day3_1;
 mod day3_2;
+mod day4;
 mod day4_1;
 mod day4_2;
+mod day5;
 mod day5_1;
 mod day5_2;
 mod day6_1;
@@ -19,27 +25,46 @@ mod day8_1;
 mod day8_2;
 mod day9_1;
 mod day9_2;
+mod graph;
+mod interval;
+mod mem;
+mod num;
+mod progress;
+mod runner;
+mod scanner;
+#[cfg(test)]
+mod testing;
+
+#[global_allocator]
+static ALLOCATOR: mem::Counting = mem::Counting;
 
 fn main() -> Result<()> {
-    day1_1::main()?;
-    day1_2::main()?;
-    day2_1::main()?;
-    day2_2::main()?;
-    day3_1::main()?;
-    day3_2::main()?;
-    day4_1::main()?;
-    day4_2::main()?;
-    day5_1::main()?;
-    // day5_2::main()?;
-    day6_1::main()?;
-    // day6_2::main()?;
-    day7_1::main()?;
-    day7_2::main()?;
-    day8_1::main()?;
-    day8_2::main()?;
-    day9_1::main()?;
-    day9_2::main()?;
-    day10_1::main()?;
+    let options = runner::Options::from_args(std::env::args().skip(1))?;
+
+    num::set_checking(options.checked);
 
-    Ok(())
+    runner::run(
+        &[
+            ("1.1", true, day1::part_1),
+            ("1.2", true, day1::part_2),
+            ("2.1", true, day2_1::main),
+            ("2.2", true, day2_2::main),
+            ("3.1", true, day3_1::main),
+            ("3.2", true, day3_2::main),
+            ("4.1", true, day4_1::main),
+            ("4.2", true, day4_2::main),
+            ("5.1", true, day5_1::main),
+            ("5.2", true, day5_2::main),
+            ("6.1", true, day6_1::main),
+            ("6.2", false, day6_2::main),
+            ("7.1", true, day7_1::main),
+            ("7.2", true, day7_2::main),
+            ("8.1", true, day8_1::main),
+            ("8.2", true, day8_2::main),
+            ("9.1", true, day9_1::main),
+            ("9.2", true, day9_2::main),
+            ("10.1", true, day10_1::main),
+        ],
+        &options,
+    )
 }
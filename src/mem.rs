@@ -0,0 +1,180 @@
+use serde::Serialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Wraps the system allocator and, once enabled, counts what passes through it.
+pub struct Counting;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+static TOTAL: AtomicU64 = AtomicU64::new(0);
+static COUNT: AtomicU64 = AtomicU64::new(0);
+
+static PHASES: Mutex<Vec<(String, Usage)>> = Mutex::new(vec![]);
+
+fn record(grown: isize, allocated: usize) {
+    let current = CURRENT.fetch_add(grown, Ordering::Relaxed) + grown;
+
+    PEAK.fetch_max(current, Ordering::Relaxed);
+    TOTAL.fetch_add(allocated as u64, Ordering::Relaxed);
+    COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+
+        if !pointer.is_null() && ENABLED.load(Ordering::Relaxed) {
+            record(layout.size() as isize, layout.size());
+        }
+
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc_zeroed(layout);
+
+        if !pointer.is_null() && ENABLED.load(Ordering::Relaxed) {
+            record(layout.size() as isize, layout.size());
+        }
+
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+
+        if ENABLED.load(Ordering::Relaxed) {
+            CURRENT.fetch_sub(layout.size() as isize, Ordering::Relaxed);
+        }
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = System.realloc(pointer, layout, new_size);
+
+        if !new_pointer.is_null() && ENABLED.load(Ordering::Relaxed) {
+            record(new_size as isize - layout.size() as isize, new_size);
+        }
+
+        new_pointer
+    }
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Heap use while a phase ran. `peak_bytes` is measured from what was live when it started.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Usage {
+    pub peak_bytes: u64,
+    pub total_bytes: u64,
+    pub allocations: u64,
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, total {} in {} allocations",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.total_bytes),
+            self.allocations
+        )
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+/// Runs `f` and records its heap use under `name` for the runner to report. Does nothing extra
+/// unless counting was enabled with `--mem`.
+pub fn phase<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    if !enabled() {
+        return f();
+    }
+
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let total = TOTAL.load(Ordering::Relaxed);
+    let count = COUNT.load(Ordering::Relaxed);
+
+    let result = f();
+
+    let usage = Usage {
+        peak_bytes: (PEAK.load(Ordering::Relaxed) - baseline).max(0) as u64,
+        total_bytes: TOTAL.load(Ordering::Relaxed) - total,
+        allocations: COUNT.load(Ordering::Relaxed) - count,
+    };
+
+    if let Ok(mut phases) = PHASES.lock() {
+        phases.push((name.to_string(), usage));
+    }
+
+    result
+}
+
+/// Hands over every phase recorded since the last call.
+pub fn take_phases() -> Vec<(String, Usage)> {
+    PHASES
+        .lock()
+        .map(|mut phases| std::mem::take(&mut *phases))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatting() {
+        let usage = Usage {
+            peak_bytes: 1536,
+            total_bytes: 3 << 20,
+            allocations: 12,
+        };
+
+        assert_eq!(
+            usage.to_string(),
+            "peak 1.5 KiB, total 3.0 MiB in 12 allocations"
+        );
+        assert_eq!(format_bytes(12), "12 B");
+    }
+
+    #[test]
+    fn counts_allocations_in_a_phase() {
+        set_enabled(true);
+
+        let kept = phase("counted", || {
+            let scratch = vec![0u8; 1 << 16];
+            drop(scratch);
+
+            vec![0u8; 4096]
+        });
+
+        let phases = take_phases();
+        let (_, usage) = phases
+            .iter()
+            .find(|(name, _)| name == "counted")
+            .expect("a recorded phase");
+
+        assert_eq!(kept.len(), 4096);
+        assert!(usage.allocations >= 2);
+        assert!(usage.total_bytes >= (1 << 16) + 4096);
+        assert!(usage.peak_bytes >= 1 << 16);
+
+        set_enabled(false);
+    }
+}
@@ -0,0 +1,222 @@
+use anyhow::Result;
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Rem, Sub};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static CHECKING: AtomicBool = AtomicBool::new(cfg!(feature = "checked"));
+
+/// Turns overflow checking on for every solver, on top of the `checked` feature.
+pub fn set_checking(enabled: bool) {
+    CHECKING.store(enabled || cfg!(feature = "checked"), Ordering::Relaxed);
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Checking for one test only, so tests running beside it keep the default.
+    static CHECKING_THIS_THREAD: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+pub fn checking() -> bool {
+    #[cfg(test)]
+    if CHECKING_THIS_THREAD.with(|checking| checking.get()) {
+        return true;
+    }
+
+    CHECKING.load(Ordering::Relaxed)
+}
+
+/// Integer types a solver can be instantiated at.
+pub trait Int:
+    Copy
+    + Ord
+    + Default
+    + Debug
+    + Display
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + TryFrom<i32>
+    + TryFrom<u32>
+    + TryFrom<u64>
+    + TryFrom<usize>
+    + 'static
+{
+    const NAME: &'static str;
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {$(
+        impl Int for $t {
+            const NAME: &'static str = stringify!($t);
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_int!(u32, u64, u128, usize, i32, i64, i128);
+
+/// Raised by the helpers below when checking is on and a result does not fit its type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub context: String,
+    pub expression: String,
+    pub ty: &'static str,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "overflow in {}: {} does not fit in {}",
+            self.context, self.expression, self.ty
+        )
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+fn overflow<T: Int>(context: &str, expression: String) -> anyhow::Error {
+    Overflow {
+        context: context.to_string(),
+        expression,
+        ty: T::NAME,
+    }
+    .into()
+}
+
+pub fn add<T: Int>(a: T, b: T, context: &str) -> Result<T> {
+    if !checking() {
+        return Ok(a + b);
+    }
+
+    a.checked_add(b)
+        .ok_or_else(|| overflow::<T>(context, format!("{a} + {b}")))
+}
+
+pub fn sub<T: Int>(a: T, b: T, context: &str) -> Result<T> {
+    if !checking() {
+        return Ok(a - b);
+    }
+
+    a.checked_sub(b)
+        .ok_or_else(|| overflow::<T>(context, format!("{a} - {b}")))
+}
+
+pub fn mul<T: Int>(a: T, b: T, context: &str) -> Result<T> {
+    if !checking() {
+        return Ok(a * b);
+    }
+
+    a.checked_mul(b)
+        .ok_or_else(|| overflow::<T>(context, format!("{a} * {b}")))
+}
+
+pub fn sum<T: Int>(values: impl IntoIterator<Item = T>, context: &str) -> Result<T> {
+    values
+        .into_iter()
+        .try_fold(T::ZERO, |acc, value| add(acc, value, context))
+}
+
+pub fn product<T: Int>(values: impl IntoIterator<Item = T>, context: &str) -> Result<T> {
+    values
+        .into_iter()
+        .try_fold(T::ONE, |acc, value| mul(acc, value, context))
+}
+
+/// Converts between integer types. Out-of-range values are always an error, checked or not.
+pub fn cast<T, V>(value: V, context: &str) -> Result<T>
+where
+    T: Int,
+    V: TryInto<T> + Copy + Display,
+{
+    value
+        .try_into()
+        .map_err(|_| overflow::<T>(context, value.to_string()))
+}
+
+/// Runs `narrow`, and if it overflowed under checking, reruns the solver with `wide` instead.
+pub fn widen<A: Display, B: Display>(
+    narrow: impl FnOnce() -> Result<A>,
+    wide: impl FnOnce() -> Result<B>,
+) -> Result<String> {
+    match narrow() {
+        Ok(value) => Ok(value.to_string()),
+        Err(error) => match error.downcast_ref::<Overflow>() {
+            Some(overflow) => {
+                eprintln!("{overflow}, rerunning at a wider type");
+
+                Ok(wide()?.to_string())
+            }
+            None => Err(error),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checked<R>(f: impl FnOnce() -> R) -> R {
+        CHECKING_THIS_THREAD.with(|checking| checking.set(true));
+        let result = f();
+        CHECKING_THIS_THREAD.with(|checking| checking.set(false));
+
+        result
+    }
+
+    #[test]
+    fn checked_arithmetic_reports_context() {
+        checked(|| {
+            assert_eq!(add(1u32, 2, "test").unwrap(), 3);
+            assert_eq!(product([2u64, 3, 7], "test").unwrap(), 42);
+
+            let error = mul(u32::MAX, 2, "doubling").unwrap_err();
+            assert_eq!(
+                error.downcast_ref::<Overflow>(),
+                Some(&Overflow {
+                    context: "doubling".to_string(),
+                    expression: "4294967295 * 2".to_string(),
+                    ty: "u32",
+                })
+            );
+            assert!(sub(0i32, i32::MIN, "test").is_err());
+            assert!(cast::<u32, _>(u64::MAX, "test").is_err());
+        });
+    }
+
+    #[test]
+    fn widen_reruns_on_overflow() {
+        let result = checked(|| {
+            widen(
+                || sum([u32::MAX, 1], "sum"),
+                || sum([u32::MAX as u128, 1], "sum"),
+            )
+        });
+
+        assert_eq!(result.unwrap(), "4294967296");
+        assert!(widen(|| Err::<u32, _>(anyhow::anyhow!("other")), || Ok(0)).is_err());
+    }
+}
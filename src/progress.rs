@@ -0,0 +1,134 @@
+use crate::runner::{self, Format};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const REDRAW_EVERY: Duration = Duration::from_millis(100);
+const WIDTH: u64 = 30;
+
+/// A live progress bar on stderr that any number of threads can advance. It draws nothing when
+/// the runner writes JSON, so stdout and stderr stay machine-readable.
+pub struct Progress {
+    label: String,
+    total: u64,
+    done: AtomicU64,
+    started: Instant,
+    last_drawn: Mutex<Option<Instant>>,
+    enabled: bool,
+}
+
+impl Progress {
+    pub fn new(label: &str, total: u64) -> Progress {
+        Progress {
+            label: label.to_string(),
+            total,
+            done: AtomicU64::new(0),
+            started: Instant::now(),
+            last_drawn: Mutex::new(None),
+            enabled: runner::format() == Format::Text,
+        }
+    }
+
+    pub fn inc(&self, amount: u64) {
+        let done = self.done.fetch_add(amount, Ordering::Relaxed) + amount;
+
+        if !self.enabled {
+            return;
+        }
+
+        // Whoever holds the lock is already drawing, so the others skip this round.
+        if let Ok(mut last_drawn) = self.last_drawn.try_lock() {
+            let now = Instant::now();
+
+            if last_drawn.is_none_or(|last| now - last >= REDRAW_EVERY) {
+                *last_drawn = Some(now);
+                self.draw(done, now);
+            }
+        }
+    }
+
+    /// Draws the final state and moves stderr past the bar.
+    pub fn finish(&self) {
+        let drawn = self.last_drawn.lock().is_ok_and(|last| last.is_some());
+
+        if self.enabled && drawn {
+            self.draw(self.done.load(Ordering::Relaxed), Instant::now());
+            eprintln!();
+        }
+    }
+
+    fn draw(&self, done: u64, now: Instant) {
+        let elapsed = (now - self.started).as_secs_f64();
+        let ratio = if self.total == 0 {
+            1.0
+        } else {
+            (done as f64 / self.total as f64).min(1.0)
+        };
+        let filled = (ratio * WIDTH as f64) as u64;
+        let rate = if elapsed > 0.0 {
+            done as f64 / elapsed
+        } else {
+            0.0
+        };
+        let eta = if rate > 0.0 {
+            format_duration(self.total.saturating_sub(done) as f64 / rate)
+        } else {
+            "--:--:--".to_string()
+        };
+
+        eprint!(
+            "\r{} [{}{}] {:>5.1}% {}/s ETA {}",
+            self.label,
+            "#".repeat(filled as usize),
+            "-".repeat((WIDTH - filled) as usize),
+            ratio * 100.0,
+            format_rate(rate),
+            eta
+        );
+    }
+}
+
+fn format_rate(rate: f64) -> String {
+    match rate {
+        r if r >= 1e9 => format!("{:.2}G", r / 1e9),
+        r if r >= 1e6 => format!("{:.2}M", r / 1e6),
+        r if r >= 1e3 => format!("{:.2}k", r / 1e3),
+        r => format!("{:.0}", r),
+    }
+}
+
+fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatting() {
+        assert_eq!(format_rate(1_234_567.0), "1.23M");
+        assert_eq!(format_rate(999.0), "999");
+        assert_eq!(format_duration(3725.4), "01:02:05");
+    }
+
+    #[test]
+    fn counts_from_many_threads() {
+        let progress = Progress::new("test", 4000);
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| (0..1000).for_each(|_| progress.inc(1)));
+            }
+        });
+
+        assert_eq!(progress.done.load(Ordering::Relaxed), 4000);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::mem;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use serde_json::json;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// The output format of the current run, so solvers know whether they may write to the terminal.
+pub fn format() -> Format {
+    if JSON.load(Ordering::Relaxed) {
+        Format::Json
+    } else {
+        Format::Text
+    }
+}
+
+static EXPLAIN: AtomicBool = AtomicBool::new(false);
+static EXPLANATIONS: Mutex<Vec<serde_json::Value>> = Mutex::new(vec![]);
+
+/// Whether solvers should say how they got their answers, with [`explain`].
+pub fn explaining() -> bool {
+    EXPLAIN.load(Ordering::Relaxed)
+}
+
+/// Shows a report on how an answer came about. Text runs print it straight away, JSON runs add it
+/// to the part's entry.
+pub fn explain(report: &(impl Serialize + Display)) {
+    match format() {
+        Format::Text => print!("{}", report),
+        Format::Json => match (EXPLANATIONS.lock(), serde_json::to_value(report)) {
+            (Ok(mut explanations), Ok(value)) => explanations.push(value),
+            _ => eprintln!("Could not record explanation"),
+        },
+    }
+}
+
+fn take_explanations() -> Vec<serde_json::Value> {
+    EXPLANATIONS
+        .lock()
+        .map(|mut explanations| std::mem::take(&mut *explanations))
+        .unwrap_or_default()
+}
+
+static PARAMS: Mutex<Vec<(String, String)>> = Mutex::new(vec![]);
+
+/// A `--param name=value` given to this run, for solvers that can solve variants of their puzzle.
+pub fn param(name: &str) -> Option<String> {
+    PARAMS
+        .lock()
+        .ok()?
+        .iter()
+        .rev()
+        .find(|(n, _)| n == name)
+        .map(|(_, value)| value.clone())
+}
+
+/// A day/part label such as `"5.2"`, whether it runs without being asked for, and its entry point.
+pub type Solution = (&'static str, bool, fn() -> Result<String>);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Options {
+    pub format: Format,
+    pub checked: bool,
+    /// Report heap use for the parse and solve phases of every part.
+    pub mem: bool,
+    /// Let solvers report how they got their answers.
+    pub explain: bool,
+    /// `name=value` pairs read by individual days, such as `vocabulary.2=digits,roman` for day 1.
+    pub params: Vec<(String, String)>,
+    /// Days (`"5"`) or parts (`"5.2"`) to run. Empty means every part that runs by default.
+    pub selection: Vec<String>,
+}
+
+impl Options {
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Options> {
+        let mut options = Options {
+            format: Format::Text,
+            checked: false,
+            mem: false,
+            explain: false,
+            params: vec![],
+            selection: vec![],
+        };
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--checked" => options.checked = true,
+                "--mem" => options.mem = true,
+                "--explain" => options.explain = true,
+                "--param" => match args.next().as_deref().and_then(|p| p.split_once('=')) {
+                    Some((name, value)) => {
+                        options.params.push((name.to_string(), value.to_string()))
+                    }
+                    None => bail!("Expected name=value after --param"),
+                },
+                "--format" => {
+                    options.format = match args.next().as_deref() {
+                        Some("text") => Format::Text,
+                        Some("json") => Format::Json,
+                        x => bail!("Expected \"text\" or \"json\" after --format, got {:?}", x),
+                    }
+                }
+                x if x.starts_with("--") => bail!("Unknown option {}", x),
+                _ => options.selection.push(arg),
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn selects(&self, label: &str, default: bool) -> bool {
+        if self.selection.is_empty() {
+            return default;
+        }
+
+        self.selection
+            .iter()
+            .any(|s| s == label || label.split('.').next() == Some(s.as_str()))
+    }
+}
+
+pub fn run(solutions: &[Solution], options: &Options) -> Result<()> {
+    JSON.store(options.format == Format::Json, Ordering::Relaxed);
+    mem::set_enabled(options.mem);
+    EXPLAIN.store(options.explain, Ordering::Relaxed);
+
+    if let Ok(mut params) = PARAMS.lock() {
+        params.clone_from(&options.params);
+    }
+
+    let mut answers = vec![];
+
+    for (label, default, solve) in solutions {
+        if !options.selects(label, *default) {
+            continue;
+        }
+
+        let answer = solve()?;
+        let phases = mem::take_phases();
+        let explanations = take_explanations();
+
+        match options.format {
+            Format::Text => {
+                println!("{}: {}", label, answer);
+
+                for (phase, usage) in phases.iter() {
+                    println!("    {}: {}", phase, usage);
+                }
+            }
+            Format::Json => {
+                let mut entry = json!({ "part": label, "answer": answer });
+
+                if options.mem {
+                    entry["memory"] = phases
+                        .into_iter()
+                        .map(|(phase, usage)| (phase, json!(usage)))
+                        .collect();
+                }
+
+                if options.explain {
+                    entry["explain"] = serde_json::Value::Array(explanations);
+                }
+
+                answers.push(entry);
+            }
+        }
+    }
+
+    if options.format == Format::Json {
+        println!("{}", serde_json::Value::Array(answers));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Options> {
+        Options::from_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn parse_options() -> Result<()> {
+        assert_eq!(
+            args(&[
+                "5.2",
+                "--format",
+                "json",
+                "--checked",
+                "7",
+                "--mem",
+                "--explain",
+                "--param",
+                "vocabulary.2=digits,roman"
+            ])?,
+            Options {
+                format: Format::Json,
+                checked: true,
+                mem: true,
+                explain: true,
+                params: vec![("vocabulary.2".to_string(), "digits,roman".to_string())],
+                selection: vec!["5.2".to_string(), "7".to_string()],
+            }
+        );
+        assert!(args(&["--format", "yaml"]).is_err());
+        assert!(args(&["--fast"]).is_err());
+        assert!(args(&["--param", "vocabulary"]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn selection() -> Result<()> {
+        let options = args(&["5", "6.2"])?;
+
+        assert!(options.selects("5.1", true));
+        assert!(options.selects("5.2", false));
+        assert!(options.selects("6.2", false));
+        assert!(!options.selects("6.1", true));
+        assert!(!options.selects("15.1", true));
+        assert!(args(&[])?.selects("1.1", true));
+        assert!(!args(&[])?.selects("5.2", false));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::VecDeque;
+
+/// A token found in a line, as a byte range and the value it stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+
+/// Finds the first and last of a set of tokens in a line in linear time. Each direction has its
+/// own Aho–Corasick automaton, so overlapping tokens like the `eight` and `two` in `eightwo`
+/// are both seen.
+pub struct Scanner {
+    forward: Automaton,
+    backward: Automaton,
+    longest: usize,
+}
+
+impl Scanner {
+    pub fn new<S: AsRef<str>>(tokens: impl IntoIterator<Item = (S, u32)>) -> Scanner {
+        let tokens: Vec<(Vec<u8>, u32)> = tokens
+            .into_iter()
+            .map(|(token, value)| (token.as_ref().as_bytes().to_vec(), value))
+            .filter(|(token, _)| !token.is_empty())
+            .collect();
+        let reversed: Vec<(Vec<u8>, u32)> = tokens
+            .iter()
+            .map(|(token, value)| (token.iter().rev().copied().collect(), *value))
+            .collect();
+
+        Scanner {
+            forward: Automaton::new(&tokens),
+            backward: Automaton::new(&reversed),
+            longest: tokens
+                .iter()
+                .map(|(token, _)| token.len())
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// The token that starts first, preferring the longest one if several start together.
+    pub fn first(&self, line: &str) -> Option<Match> {
+        let mut best: Option<Match> = None;
+
+        for (end, len, value) in self.forward.matches(line.bytes()) {
+            let found = Match {
+                start: end + 1 - len,
+                end: end + 1,
+                value,
+            };
+
+            if best.is_none_or(|b| (found.start, b.end) < (b.start, found.end)) {
+                best = Some(found);
+            }
+
+            // Nothing ending further on can start before the best match any more.
+            if best.is_some_and(|b| end >= b.start + self.longest) {
+                break;
+            }
+        }
+
+        best
+    }
+
+    /// The token that ends last, preferring the longest one if several end together.
+    pub fn last(&self, line: &str) -> Option<Match> {
+        let bytes = line.as_bytes();
+        let mut best: Option<Match> = None;
+
+        for (offset, len, value) in self.backward.matches(bytes.iter().rev().copied()) {
+            let found = Match {
+                start: bytes.len() - 1 - offset,
+                end: bytes.len() - 1 - offset + len,
+                value,
+            };
+
+            if best.is_none_or(|b| (b.end, found.start) < (found.end, b.start)) {
+                best = Some(found);
+            }
+
+            if best.is_some_and(|b| offset + b.end >= bytes.len() + self.longest) {
+                break;
+            }
+        }
+
+        best
+    }
+}
+
+/// A complete transition table, so scanning takes one lookup per byte.
+struct Automaton {
+    next: Vec<[usize; 256]>,
+    /// `(length, value)` of every token ending in each state, following suffix links.
+    outputs: Vec<Vec<(usize, u32)>>,
+}
+
+impl Automaton {
+    fn new(tokens: &[(Vec<u8>, u32)]) -> Automaton {
+        const MISSING: usize = usize::MAX;
+
+        let mut next = vec![[MISSING; 256]];
+        let mut outputs = vec![vec![]];
+
+        for (token, value) in tokens {
+            let mut state = 0;
+
+            for &byte in token {
+                if next[state][byte as usize] == MISSING {
+                    next[state][byte as usize] = next.len();
+                    next.push([MISSING; 256]);
+                    outputs.push(vec![]);
+                }
+
+                state = next[state][byte as usize];
+            }
+
+            outputs[state].push((token.len(), *value));
+        }
+
+        let mut fail = vec![0; next.len()];
+        let mut queue = VecDeque::new();
+
+        for slot in next[0].iter_mut() {
+            match *slot {
+                MISSING => *slot = 0,
+                child => queue.push_back(child),
+            }
+        }
+
+        // Breadth first, so every suffix state is complete before its longer states use it.
+        while let Some(state) = queue.pop_front() {
+            let inherited = outputs[fail[state]].clone();
+            outputs[state].extend(inherited);
+
+            let fallbacks = next[fail[state]];
+
+            for (slot, fallback) in next[state].iter_mut().zip(fallbacks) {
+                match *slot {
+                    MISSING => *slot = fallback,
+                    child => {
+                        fail[child] = fallback;
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+
+        Automaton { next, outputs }
+    }
+
+    /// Every `(index of last byte, length, value)` in order of where the tokens end.
+    fn matches<'a>(
+        &'a self,
+        bytes: impl Iterator<Item = u8> + 'a,
+    ) -> impl Iterator<Item = (usize, usize, u32)> + 'a {
+        bytes
+            .enumerate()
+            .scan(0, |state, (index, byte)| {
+                *state = self.next[*state][byte as usize];
+
+                Some((index, *state))
+            })
+            .flat_map(|(index, state)| {
+                self.outputs[state]
+                    .iter()
+                    .map(move |&(len, value)| (index, len, value))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_words() {
+        let words = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        let scanner = Scanner::new(words.into_iter().zip(1..).chain([("7", 7)]));
+
+        let first = scanner.first("xeightwo").unwrap();
+        let last = scanner.last("xeightwo").unwrap();
+
+        assert_eq!(
+            first,
+            Match {
+                start: 1,
+                end: 6,
+                value: 8
+            }
+        );
+        assert_eq!(
+            last,
+            Match {
+                start: 5,
+                end: 8,
+                value: 2
+            }
+        );
+        assert_eq!(scanner.first("abc"), None);
+        assert_eq!(scanner.last("7"), scanner.first("7"));
+    }
+
+    #[test]
+    fn prefers_earliest_start_over_earliest_end() {
+        let scanner = Scanner::new([("abcd", 1), ("bc", 2), ("cd", 3), ("d", 4)]);
+
+        assert_eq!(scanner.first("xabcd").map(|m| m.value), Some(1));
+        assert_eq!(scanner.last("abcdx").map(|m| m.value), Some(1));
+        assert_eq!(scanner.last("abcx").map(|m| m.value), Some(2));
+    }
+}
@@ -0,0 +1,25 @@
+//! Helpers shared by tests. Benchmarks are ignored tests named `bench_*`, run with
+//! `cargo test --release bench -- --ignored --nocapture`.
+
+/// A xorshift generator, so generated inputs are the same on every run without a crate for it.
+pub struct Xorshift(u64);
+
+impl Default for Xorshift {
+    fn default() -> Xorshift {
+        Xorshift(0x2545f491)
+    }
+}
+
+impl Xorshift {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..bound`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}